@@ -1,45 +1,120 @@
 // actions.rs
 
+use std::cmp::Ordering;
+use std::fs;
 use std::io::{self, Write};
-use crate::locale::LangStrings;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
 use crate::{AppData, command_exist};
+use crate::base64;
 use crate::clipboard;
+use crate::locale::LangStrings;
+use crate::picker;
+use crate::date;
+use crate::domain;
+use crate::entry::{self, Entry};
+use crate::generator;
+use crate::get_pw;
+use crate::journal;
+use crate::otp;
+use crate::qr;
+use crate::search::{self, Query};
+use crate::template;
+use crate::parse::{Expiry, ListOptions, Selector};
 
-fn scan(ptrn: &String, list: &Vec<String>) -> Vec<usize> {
-	// Retourne en vecteur d'index les lignes qui match le pattern.
-	let pattern_lower = ptrn.to_lowercase();
+const MASQUE: &str = "********";
+const MAX_ATTACHMENT: usize = 512 * 1024;		// Octets par pièce jointe
+const MAX_ATTACHMENTS: usize = 2 * 1024 * 1024;	// Octets par entrée, toutes pièces jointes comprises
 
-	let mut x = 0;
-	let mut y = 0;
-	let mut selected_vs_full = Vec::new();
+/// Issue d'une commande qui choisit une entrée.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+	Unchanged,	// Consultation ou annulation: rien à sauvegarder
+	Changed,	// Les entrées ont été modifiées: à sauvegarder
+	NotFound,	// Aucune entrée ne correspond
+	Ambiguous,	// Plusieurs entrées correspondent à une sélection non interactive
+	Failed,		// La commande n'a pu aboutir (erreur déjà affichée): rien à sauvegarder
+}
 
-	for ligne in list {
-		if ligne.to_lowercase().contains(&pattern_lower) {
-			selected_vs_full.push(y);
-			if let Some((avant, apres)) = ligne.split_once("∫∆∫") {
-				x += 1;		// On incrémente x si nous avons une correspondance
-				println!("{x:3}: {apres} ==> password: {avant}"); 
-			}
-		}
-		y += 1;		// On incrémente y peut importe
+// Titre précédé du dossier de l'entrée: 'travail/aws/Console'.
+fn chemin_titre(entry: &Entry) -> String {
+	match entry.folder() {
+		Some(dossier) => format!("{dossier}/{}", entry.title),
+		None => entry.title.clone(),
+	}
+}
+
+fn affiche_ligne(x: usize, entry: &Entry, show: bool) {
+	// Utilisateur et URL d'abord; les champs secrets du type sont masqués comme le mot de passe
+	let mut details: Vec<String> = [entry::USER, entry::URL].into_iter()
+		.filter_map(|cle| entry.get(cle).map(|valeur| format!("{cle}: {valeur}")))
+		.collect();
+	details.extend(template::display_fields(entry).into_iter()
+		.map(|(nom, valeur, secret)| {
+			let valeur = if secret && !show { MASQUE.to_string() } else { valeur.replace('\n', " | ") };
+			format!("{nom}: {valeur}")
+		}));
+	let jointes: Vec<&str> = entry.attachments().into_iter().map(|(nom, _)| nom).collect();
+	if !jointes.is_empty() {
+		details.push(format!("attachments: {}", jointes.join(", ")));
+	}
+	let modele = template::of(entry);
+	let titre = if modele.name == template::LOGIN { chemin_titre(entry) } else { format!("[{}] {}", modele.name, chemin_titre(entry)) };
+	if details.is_empty() {
+		println!("{x:3}: {titre}");
+	}
+	else {
+		println!("{x:3}: {titre} ==> {}", details.join(", "));
+	}
+}
+
+fn scan(query: &Query, list: &[Entry], show: bool) -> Vec<usize> {
+	// Retourne en vecteur d'index les entrées qui match le pattern, les plus pertinentes d'abord.
+	// Seuls les champs descriptifs sont consultés, jamais le mot de passe.
+	let selected_vs_full = search::rank(query, list);
+
+	for (x, &y) in selected_vs_full.iter().enumerate() {
+		// x est la position dans la liste affichée, débutant à 1
+		affiche_ligne(x + 1, &list[y], show);
 	}
 	selected_vs_full
 }
 
-fn get_index(max_len: usize, loc: &LangStrings) -> usize {
-	// Demande à l'usager quelle ligne traiter.
-	let mut input = String::new();
+fn read_answer(prompt: &str, trouve: &[usize], list: &[Entry], data: &AppData) -> String {
+	// Demande à l'usager quelle(s) ligne(s) traiter.
+	// Une saisie '?N' affiche le mot de passe de l'entrée N puis redemande.
+	loop {
+		print!("{prompt}{}", data.app_locale.reveal_hint);
+		let _ = io::stdout().flush();
+
+		let mut input = String::new();
+
+		// 1. Lire la saisie
+		io::stdin()
+			.read_line(&mut input)
+			.expect(data.app_locale.err_keyboard);
 
-	// 1. Lire la saisie
-	io::stdin()
-		.read_line(&mut input)
-		.expect(format!("{}", loc.err_keyboard).as_str());
+		let saisie = input.trim();
+		match saisie.strip_prefix('?').map(|reste| reste.trim().parse::<usize>()) {
+			Some(Ok(index)) if index > 0 && index <= trouve.len() => {
+				affiche_ligne(index, &list[trouve[index - 1]], true);
+			}
+			Some(Ok(index)) => {
+				println!("{}", data.app_locale.err_index_too_big.replace("{1}", index.to_string().as_str()));
+			}
+			Some(Err(_)) => { }
+			None => return saisie.to_string(),
+		}
+	}
+}
 
+fn get_index(prompt: &str, trouve: &[usize], list: &[Entry], data: &AppData) -> usize {
 	// 2. Tenter de convertir en nombre (usize est idéal pour les index)
-	match input.trim().parse::<usize>() {
+	match read_answer(prompt, trouve, list, data).parse::<usize>() {
 		Ok(index) => {
-			if index > max_len { 
-				println!("{}", loc.err_index_too_big.replace("{1}", index.to_string().as_str()));
+			if index > trouve.len() {
+				println!("{}", data.app_locale.err_index_too_big.replace("{1}", index.to_string().as_str()));
 				return 0;
 			}
 			else { return index; }
@@ -48,57 +123,1398 @@ fn get_index(max_len: usize, loc: &LangStrings) -> usize {
 	}
 }
 
-pub fn find(ptrn: &String, data: &AppData) {
+/// Décode une liste de positions: '1,3,5-7', ou 'all' (aussi 'tout', 'todo', '*').
+/// Retourne les positions (débutant à 1) triées sans doublon, ou la première position invalide.
+fn parse_positions(saisie: &str, max: usize) -> Result<Vec<usize>, String> {
+	if matches!(saisie.to_lowercase().as_str(), "all" | "tout" | "todo" | "*") {
+		return Ok((1..=max).collect());
+	}
+	let mut positions = Vec::new();
+	for morceau in saisie.split(',').map(str::trim).filter(|m| !m.is_empty()) {
+		let (debut, fin) = match morceau.split_once('-') {
+			Some((d, f)) => (d.trim(), f.trim()),
+			None => (morceau, morceau),
+		};
+		match (debut.parse::<usize>(), fin.parse::<usize>()) {
+			(Ok(d), Ok(f)) if d >= 1 && d <= f && f <= max => positions.extend(d..=f),
+			_ => return Err(morceau.to_string()),
+		}
+	}
+	positions.sort_unstable();
+	positions.dedup();
+	Ok(positions)
+}
+
+fn get_indices(prompt: &str, trouve: &[usize], list: &[Entry], data: &AppData) -> Vec<usize> {
+	match parse_positions(&read_answer(prompt, trouve, list, data), trouve.len()) {
+		Ok(positions) => positions,
+		Err(morceau) => {
+			println!("{}", data.app_locale.err_bad_selection.replace("{1}", &morceau));
+			Vec::new()
+		}
+	}
+}
+
+fn labels_secrets(trouve: &[usize], list: &[Entry]) -> (Vec<String>, Vec<String>) {
+	let labels = trouve.iter().map(|&i| list[i].title.clone()).collect();
+	let secrets = trouve.iter().map(|&i| template::main_secret(&list[i]).to_string()).collect();
+	(labels, secrets)
+}
+
+fn choose(prompt: &str, trouve: &[usize], list: &[Entry], data: &AppData) -> usize {
+	// Choix d'une entrée parmi celles trouvées: sélecteur plein écran sur un terminal,
+	// sinon saisie du numéro. Retourne la position (débutant à 1) ou 0 pour annuler.
+	if !picker::available() {
+		return get_index(prompt, trouve, list, data);
+	}
+	let (labels, secrets) = labels_secrets(trouve, list);
+	match picker::pick(prompt, &labels, &secrets, &data.app_locale) {
+		Some(position) => position + 1,
+		None => 0,
+	}
+}
+
+fn choose_many(prompt: &str, trouve: &[usize], list: &[Entry], data: &AppData) -> Vec<usize> {
+	// Comme 'choose', pour plusieurs entrées. Une liste vide signifie l'annulation.
+	if !picker::available() {
+		return get_indices(prompt, trouve, list, data);
+	}
+	let (labels, secrets) = labels_secrets(trouve, list);
+	match picker::pick_many(prompt, &labels, &secrets, &data.app_locale) {
+		Some(positions) => positions.into_iter().map(|p| p + 1).collect(),
+		None => Vec::new(),
+	}
+}
+
+/// Demande une confirmation oui/non (non par défaut). '--yes' répond oui d'office.
+fn confirm(prompt: &str, data: &AppData) -> bool {
+	if data.app_assume_yes { return true; }
+	print!("{prompt} {} ", data.app_locale.yes_no);
+	let _ = io::stdout().flush();
+
+	let mut input = String::new();
+	if io::stdin().read_line(&mut input).is_err() { return false; }
+	let reponse = input.trim().to_lowercase();
+	data.app_locale.yes_answers.split(',').any(|oui| oui == reponse)
+}
+
+fn select(prompt: &str, trouve: &[usize], list: &[Entry], data: &AppData) -> Result<usize, Outcome> {
+	// Position (débutant à 1) de l'entrée choisie, selon le mode de sélection.
+	// Les modes non interactifs ne lisent jamais le clavier: ils échouent plutôt.
+	match &data.app_selector {
+		Selector::Interactive => match choose(prompt, trouve, list, data) {
+			0 => Err(Outcome::Unchanged),
+			position => Ok(position),
+		},
+		Selector::First => Ok(1),
+		Selector::Index(n) => {
+			if *n > trouve.len() {
+				eprintln!("{}", data.app_locale.err_index_too_big.replace("{1}", n.to_string().as_str()));
+				return Err(Outcome::NotFound);
+			}
+			Ok(*n)
+		}
+		Selector::Exact(titre) => {
+			let exactes: Vec<usize> = (1..=trouve.len())
+				.filter(|&p| list[trouve[p - 1]].title == *titre)
+				.collect();
+			match exactes.as_slice() {
+				[] => {
+					eprintln!("{}", data.app_locale.no_match.replace("{1}", titre));
+					Err(Outcome::NotFound)
+				}
+				[position] => Ok(*position),
+				_ => {
+					eprintln!("{}", data.app_locale.err_ambiguous.replace("{1}", exactes.len().to_string().as_str()));
+					Err(Outcome::Ambiguous)
+				}
+			}
+		}
+	}
+}
+
+fn select_many(prompt: &str, trouve: &[usize], list: &[Entry], data: &AppData) -> Result<Vec<usize>, Outcome> {
+	// Positions choisies; les modes non interactifs n'en choisissent qu'une.
+	match data.app_selector {
+		Selector::Interactive => match choose_many(prompt, trouve, list, data) {
+			positions if positions.is_empty() => Err(Outcome::Unchanged),
+			positions => Ok(positions),
+		},
+		_ => select(prompt, trouve, list, data).map(|p| vec![p]),
+	}
+}
+
+pub fn find(query: &Query, data: &mut AppData) -> Outcome {
 	println!("----------------");
-	let trouve = scan(&ptrn, &data.app_line_vec);
+	let trouve = scan(query, &data.app_entries, data.app_show_pw);
 	if trouve.is_empty() {
-		println!("\t------ {}", data.app_locale.no_match.replace("{1}", ptrn));
+		println!("\t------ {}", data.app_locale.no_match.replace("{1}", query.source()));
 		println!("----------------");
+		return Outcome::NotFound;
+	}
+	println!("----------------");
+	return reveal(&trouve, data);
+}
+
+/// Entrées dont une URL correspond à celle donnée, au moins au degré 'niveau':
+/// la correspondance la plus stricte d'abord, puis la plus récemment utilisée.
+/// Le choix se fait ensuite comme avec --find.
+pub fn find_url(url: &str, niveau: domain::Match, data: &mut AppData) -> Outcome {
+	let cherche = domain::host(url).unwrap_or_default();
+	let mut trouve: Vec<(usize, domain::Match)> = data.app_entries.iter().enumerate()
+		.filter_map(|(i, e)| {
+			// Le champ url peut contenir plusieurs adresses, séparées par des blancs
+			let meilleur = e.get(entry::URL)?.split_whitespace()
+				.filter_map(domain::host)
+				.filter_map(|hote| domain::compare(&cherche, &hote))
+				.max()?;
+			(meilleur >= niveau).then_some((i, meilleur))
+		})
+		.collect();
+	trouve.sort_by_key(|&(i, m)| std::cmp::Reverse((m, data.app_entries[i].get_time(entry::USED))));
+	let trouve: Vec<usize> = trouve.into_iter().map(|(i, _)| i).collect();
+
+	println!("----------------");
+	if trouve.is_empty() {
+		println!("\t------ {}", data.app_locale.url_no_match.replace("{1}", &cherche));
+		println!("----------------");
+		return Outcome::NotFound;
+	}
+	for (x, &i) in trouve.iter().enumerate() {
+		affiche_ligne(x + 1, &data.app_entries[i], data.app_show_pw);
+	}
+	println!("----------------");
+	return reveal(&trouve, data);
+}
+
+// Copie (ou affiche, faute de presse-papier) le secret de l'entrée choisie parmi celles trouvées.
+fn reveal(trouve: &[usize], data: &mut AppData) -> Outcome {
+	let presse_papier = command_exist("pbcopy", &data.app_locale);
+	// Rien à demander: les mots de passe sont déjà affichés et il n'y a pas de presse-papier
+	if !presse_papier && data.app_show_pw && data.app_selector == Selector::Interactive {
+		return Outcome::Unchanged;
+	}
+	let prompt = if presse_papier { data.app_locale.index_to_clip } else { data.app_locale.index_to_show };
+
+	let position = match select(prompt, trouve, &data.app_entries, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+	let i = trouve[position - 1];
+	if presse_papier {
+		clipboard::send_to_clipboard(template::main_secret(&data.app_entries[i]));
 	}
 	else {
-		if command_exist("pbcopy", &data.app_locale) {
-			println!("----------------");
-			print!("{}", data.app_locale.index_to_clip);
-			let _ = io::stdout().flush();
-
-			let index = get_index(trouve.len(), &data.app_locale);
-			if index > 0 {
-				if let Some((pw, _)) = trouve.get(index - 1)
-					.and_then(|&i| data.app_line_vec[i].split_once("∫∆∫"))
-				{
-					clipboard::send_to_clipboard(pw);
-				}
+		affiche_ligne(position, &data.app_entries[i], true);
+	}
+	data.app_entries[i].touch_used();
+	return Outcome::Changed;
+}
+
+pub fn del(query: &Query, data: &mut AppData) -> Outcome {
+	println!("----------------");
+	let trouve = scan(query, &data.app_entries, data.app_show_pw);
+	if trouve.is_empty() {
+		println!("\t------ {}", data.app_locale.no_match.replace("{1}", query.source()));
+		println!("----------------");
+		return Outcome::NotFound;
+	}
+	println!("----------------");
+
+	let positions = match select_many(data.app_locale.index_to_del, &trouve, &data.app_entries, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+
+	// Rappel des entrées choisies avant confirmation
+	for &p in &positions {
+		println!("\t- {}", data.app_entries[trouve[p - 1]].title);
+	}
+	let question = data.app_locale.del_confirm.replace("{1}", positions.len().to_string().as_str());
+	if !confirm(&question, data) {
+		println!("{}", data.app_locale.del_cancel);
+		return Outcome::Unchanged;
+	}
+
+	// Déplacement vers la corbeille, de la fin vers le début pour garder les index valides
+	let mut a_supprimer: Vec<usize> = positions.iter().map(|&p| trouve[p - 1]).collect();
+	a_supprimer.sort_unstable_by(|a, b| b.cmp(a));
+	let maintenant = date::now().to_string();
+	for index in a_supprimer {
+		let mut supprime = data.app_entries.remove(index);
+		println!("{}", data.app_locale.del_success.replace("{1}", &supprime.title));
+		supprime.set(entry::DELETED, &maintenant);
+		data.app_trash.push(supprime);
+	}
+	return Outcome::Changed;
+}
+
+// Choix sans liste ni question, pour une sortie destinée à un script: la seule entrée trouvée,
+// ou la seule dont le titre est le nom demandé, sauf sélecteur explicite.
+fn select_quiet(query: &Query, trouve: &[usize], data: &AppData) -> Result<usize, Outcome> {
+	if data.app_selector != Selector::Interactive {
+		return select("", trouve, &data.app_entries, data);
+	}
+	let cible = search::normalize(query.source(), false);
+	let exactes: Vec<usize> = (1..=trouve.len())
+		.filter(|&p| search::normalize(&data.app_entries[trouve[p - 1]].title, false) == cible)
+		.collect();
+	if trouve.len() == 1 { return Ok(1); }
+	if let [position] = exactes.as_slice() { return Ok(*position); }
+	eprintln!("{}", data.app_locale.err_ambiguous.replace("{1}", trouve.len().to_string().as_str()));
+	Err(Outcome::Ambiguous)
+}
+
+/// Affiche seulement la valeur d'un champ de l'entrée demandée, sans décoration,
+/// pour les scripts: 'curl -u me:$(mdp -g api)'. N'interagit jamais avec l'usager.
+/// Lecture seule: le fichier n'est réécrit que si le compteur d'un code HOTP avance.
+pub fn get(query: &Query, field: &str, data: &mut AppData) -> Outcome {
+	let trouve = search::rank(query, &data.app_entries);
+	if trouve.is_empty() {
+		eprintln!("{}", data.app_locale.no_match.replace("{1}", query.source()));
+		return Outcome::NotFound;
+	}
+
+	let position = match select_quiet(query, &trouve, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+
+	let entry = &mut data.app_entries[trouve[position - 1]];
+	let otp_avant = entry.get(entry::OTP).map(str::to_string);
+	let valeur = match field {
+		"password" => Some(entry.pw.clone()),
+		entry::OTP => otp_code(entry).map(|(code, _)| code),
+		_ => entry.get(field).or_else(|| entry.custom(field)).map(str::to_string),
+	};
+	let Some(valeur) = valeur.filter(|v| !v.is_empty()) else {
+		eprintln!("{}", data.app_locale.err_no_field.replace("{1}", &entry.title).replace("{2}", field));
+		return Outcome::NotFound;
+	};
+
+	print!("{valeur}");
+	// Saut de ligne seulement pour un humain: '$(...)' reçoit la valeur brute
+	if get_pw::is_tty(io::stdout().as_raw_fd()) { println!(); }
+	let _ = io::stdout().flush();
+
+	if entry.get(entry::OTP) != otp_avant.as_deref() { return Outcome::Changed; }
+	return Outcome::Unchanged;
+}
+
+fn affiche_corbeille(x: usize, entry: &Entry, loc: &LangStrings) {
+	let quand = entry.get_time(entry::DELETED).map(date::format_date).unwrap_or_default();
+	println!("{x:3}: {} -- {} {quand}", entry.title, loc.trash_deleted_on);
+}
+
+pub fn list_trash(data: &AppData) {
+	println!("----------------");
+	if data.app_trash.is_empty() {
+		println!("{}", data.app_locale.trash_empty);
+	}
+	for (x, entry) in data.app_trash.iter().enumerate() {
+		affiche_corbeille(x + 1, entry, &data.app_locale);
+	}
+	println!("----------------");
+}
+
+/// Étiquettes des entrées et leur nombre d'entrées, par ordre alphabétique.
+/// La casse et les accents sont ignorés: 'Travail' et 'travail' ne font qu'une.
+pub fn list_tags(data: &AppData) {
+	let mut etiquettes: Vec<(&str, usize)> = Vec::new();
+	for t in data.app_entries.iter().flat_map(Entry::tags) {
+		match etiquettes.iter_mut().find(|(e, _)| search::normalize(e, false) == search::normalize(t, false)) {
+			Some((_, n)) => *n += 1,
+			None => etiquettes.push((t, 1)),
+		}
+	}
+	etiquettes.sort_by_key(|(e, _)| search::normalize(e, false));
+
+	println!("----------------");
+	if etiquettes.is_empty() {
+		println!("{}", data.app_locale.tags_empty);
+	}
+	for (t, n) in etiquettes {
+		println!("    {t} ({n})");
+	}
+	println!("----------------");
+}
+
+/// Arbre des dossiers; le nombre d'un dossier compte aussi ses sous-dossiers.
+pub fn list_folders(data: &AppData) {
+	let mut dossiers: Vec<(Vec<&str>, usize)> = Vec::new();
+	let mut sans_dossier = 0;
+	for entry in &data.app_entries {
+		let Some(chemin) = entry.folder() else {
+			sans_dossier += 1;
+			continue;
+		};
+		let segments: Vec<&str> = chemin.split('/').collect();
+		for n in 1..=segments.len() {
+			match dossiers.iter_mut().find(|(d, _)| d[..] == segments[..n]) {
+				Some((_, compte)) => *compte += 1,
+				None => dossiers.push((segments[..n].to_vec(), 1)),
 			}
 		}
 	}
+	// Tri segment par segment: un dossier est suivi de ses sous-dossiers
+	dossiers.sort_by_key(|(d, _)| d.iter().map(|s| search::normalize(s, false)).collect::<Vec<_>>());
+
+	println!("----------------");
+	if dossiers.is_empty() {
+		println!("{}", data.app_locale.folders_empty);
+	}
+	else {
+		for (d, compte) in &dossiers {
+			println!("    {}{} ({compte})", "    ".repeat(d.len() - 1), d[d.len() - 1]);
+		}
+		if sans_dossier > 0 {
+			println!("    {} ({sans_dossier})", data.app_locale.folder_none);
+		}
+	}
+	println!("----------------");
+}
+
+// Valeur d'une colonne de --list. Les colonnes possibles sont toutes descriptives: jamais de secret.
+fn colonne(entry: &Entry, nom: &str) -> String {
+	match nom {
+		entry::TAGS => entry.tags().join(", "),
+		entry::TYPE => template::of(entry).name.to_string(),
+		entry::CREATED | entry::MODIFIED | entry::USED => entry.get_time(nom).map(date::format_date).unwrap_or_default(),
+		"due" => entry.due().map(date::format_day).unwrap_or_default(),
+		_ => entry.get(nom).unwrap_or_default().to_string(),
+	}
 }
 
-pub fn del(ptrn: &String, data: &mut AppData) -> bool {
+fn segments_dossier(entry: &Entry) -> Vec<String> {
+	entry.folder().unwrap_or_default().split('/')
+		.filter(|s| !s.is_empty())
+		.map(|s| search::normalize(s, false))
+		.collect()
+}
+
+// Ordre de --sort; à égalité, par titre. Les dates vont de la plus récente à la plus ancienne,
+// les entrées sans date en dernier.
+fn compare(a: &Entry, b: &Entry, tri: &str) -> Ordering {
+	let ordre = match tri {
+		entry::FOLDER => segments_dossier(a).cmp(&segments_dossier(b)),
+		entry::TYPE => template::of(a).name.cmp(template::of(b).name),
+		entry::CREATED | entry::MODIFIED | entry::USED => b.get_time(tri).cmp(&a.get_time(tri)),
+		// Échéance la plus proche d'abord
+		"due" => match (a.due(), b.due()) {
+			(Some(x), Some(y)) => x.cmp(&y),
+			(Some(_), None) => Ordering::Less,
+			(None, Some(_)) => Ordering::Greater,
+			(None, None) => Ordering::Equal,
+		},
+		_ => Ordering::Equal,
+	};
+	ordre.then_with(|| search::normalize(&a.title, false).cmp(&search::normalize(&b.title, false)))
+}
+
+/// Liste les entrées qui correspondent au motif, en tableau aligné ou en arbre de dossiers.
+/// Seuls le titre et les colonnes descriptives demandées sont montrés, jamais un secret.
+/// Avec 'older_than', seulement les entrées sans modification depuis plus de N jours (audit);
+/// une entrée sans date de modification ni de création est comptée comme ancienne.
+pub fn list(query: &Query, options: &ListOptions, older_than: Option<u64>, data: &AppData) -> Outcome {
+	let tri = options.sort.as_deref().unwrap_or("title");
+	let limite = date::now().saturating_sub(older_than.unwrap_or(0) * date::SECS_PER_DAY);
+	let ancienne = |e: &Entry| older_than.is_none()
+		|| e.get_time(entry::MODIFIED).or(e.get_time(entry::CREATED)).is_none_or(|t| t <= limite);
+	let mut trouve: Vec<&Entry> = search::rank(query, &data.app_entries).into_iter()
+		.map(|i| &data.app_entries[i])
+		.filter(|e| ancienne(e))
+		.collect();
+	trouve.sort_by(|a, b| {
+		// En arbre, les entrées restent groupées par dossier: le tri s'applique dans chaque dossier
+		let dossier = if options.tree { segments_dossier(a).cmp(&segments_dossier(b)) } else { Ordering::Equal };
+		let ordre = compare(a, b, tri);
+		dossier.then(if options.reverse { ordre.reverse() } else { ordre })
+	});
+
 	println!("----------------");
-	let trouve = scan(&ptrn, &data.app_line_vec);
 	if trouve.is_empty() {
-		println!("\t------ {}", data.app_locale.no_match.replace("{1}", ptrn));
+		println!("{}", data.app_locale.list_empty);
 		println!("----------------");
-		return false;
+		return Outcome::NotFound;
 	}
-	else {
+
+	// Lignes à afficher: None pour un nom de dossier (arbre), sinon les cellules de l'entrée
+	let mut lignes: Vec<(String, Option<Vec<String>>)> = Vec::new();
+	let mut precedent: Vec<&str> = Vec::new();
+	for entry in &trouve {
+		let mut retrait = 0;
+		if options.tree {
+			let chemin: Vec<&str> = entry.folder().unwrap_or_default().split('/').filter(|s| !s.is_empty()).collect();
+			let commun = chemin.iter().zip(&precedent)
+				.take_while(|(a, b)| search::normalize(a, false) == search::normalize(b, false))
+				.count();
+			for (n, segment) in chemin.iter().enumerate().skip(commun) {
+				lignes.push((format!("{}{segment}/", "    ".repeat(n)), None));
+			}
+			retrait = chemin.len();
+			precedent = chemin;
+		}
+		let cellules = options.columns.iter().map(|c| colonne(entry, c)).collect();
+		lignes.push((format!("{}{}", "    ".repeat(retrait), entry.title), Some(cellules)));
+	}
+
+	// Largeur de chaque colonne, en caractères
+	let mut largeurs: Vec<usize> = std::iter::once("title").chain(options.columns.iter().map(String::as_str))
+		.map(|c| c.chars().count())
+		.collect();
+	for (titre, cellules) in &lignes {
+		let Some(cellules) = cellules else { continue };
+		largeurs[0] = largeurs[0].max(titre.chars().count());
+		for (n, c) in cellules.iter().enumerate() {
+			largeurs[n + 1] = largeurs[n + 1].max(c.chars().count());
+		}
+	}
+	let affiche = |cellules: Vec<&str>| {
+		let texte: Vec<String> = cellules.iter().zip(&largeurs).map(|(c, &l)| format!("{c:<l$}")).collect();
+		println!("{}", texte.join("  ").trim_end());
+	};
+
+	if !options.columns.is_empty() {
+		affiche(std::iter::once("title").chain(options.columns.iter().map(String::as_str)).collect());
+	}
+	for (titre, cellules) in &lignes {
+		match cellules {
+			Some(cellules) => affiche(std::iter::once(titre.as_str()).chain(cellules.iter().map(String::as_str)).collect()),
+			None => println!("{titre}"),
+		}
+	}
+	println!("----------------");
+	println!("{}", data.app_locale.list_count.replace("{1}", trouve.len().to_string().as_str()));
+	return Outcome::Unchanged;
+}
+
+pub fn undelete(query: &Query, data: &mut AppData) -> Outcome {
+	println!("----------------");
+	let trouve = search::rank(query, &data.app_trash);
+	if trouve.is_empty() {
+		println!("\t------ {}", data.app_locale.no_match.replace("{1}", query.source()));
 		println!("----------------");
-		print!("{}", data.app_locale.index_to_del);
+		return Outcome::NotFound;
+	}
+	for (x, &i) in trouve.iter().enumerate() {
+		affiche_corbeille(x + 1, &data.app_trash[i], &data.app_locale);
+	}
+	println!("----------------");
+
+	let positions = match select_many(data.app_locale.index_to_restore, &trouve, &data.app_trash, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+
+	// Retour vers les entrées, de la fin vers le début pour garder les index valides
+	let mut a_restaurer: Vec<usize> = positions.iter().map(|&p| trouve[p - 1]).collect();
+	a_restaurer.sort_unstable_by(|a, b| b.cmp(a));
+	for index in a_restaurer {
+		let mut restaure = data.app_trash.remove(index);
+		restaure.set(entry::DELETED, "");
+		println!("{}", data.app_locale.restore_success.replace("{1}", &restaure.title));
+		data.app_entries.push(restaure);
+	}
+	return Outcome::Changed;
+}
+
+/// Vide la corbeille; avec 'older_than', seulement les entrées supprimées depuis plus de N jours.
+pub fn empty_trash(older_than: Option<u64>, data: &mut AppData) -> Outcome {
+	let limite = date::now().saturating_sub(older_than.unwrap_or(0) * date::SECS_PER_DAY);
+	let a_purger = |e: &Entry| older_than.is_none() || e.get_time(entry::DELETED).is_some_and(|t| t <= limite);
+
+	if data.app_trash.is_empty() {
+		println!("{}", data.app_locale.trash_empty);
+		return Outcome::Unchanged;
+	}
+	let nombre = data.app_trash.iter().filter(|e| a_purger(e)).count();
+	if nombre == 0 {
+		println!("{}", data.app_locale.purge_success.replace("{1}", "0"));
+		return Outcome::Unchanged;
+	}
+	let question = data.app_locale.purge_confirm.replace("{1}", nombre.to_string().as_str());
+	if !confirm(&question, data) {
+		println!("{}", data.app_locale.del_cancel);
+		return Outcome::Unchanged;
+	}
+	let (detruites, gardees): (Vec<Entry>, Vec<Entry>) = std::mem::take(&mut data.app_trash)
+		.into_iter()
+		.partition(|e| a_purger(e));
+	data.app_trash = gardees;
+	journal::forget(&mut data.app_journal, &detruites);
+	println!("{}", data.app_locale.purge_success.replace("{1}", nombre.to_string().as_str()));
+	return Outcome::Changed;
+}
+
+fn titres(entries: &[Entry]) -> String {
+	entries.iter().map(|e| e.title.as_str()).collect::<Vec<_>>().join(", ")
+}
+
+/// Annule les 'n' dernières opérations du journal, de la plus récente à la plus ancienne.
+pub fn undo(n: usize, data: &mut AppData) -> Outcome {
+	if data.app_journal.is_empty() {
+		println!("{}", data.app_locale.undo_empty);
+		return Outcome::Unchanged;
+	}
+	// Les opérations sont annulées sur une copie: si l'une ne peut l'être, rien ne change
+	let (mut entries, mut corbeille, mut journal) = (data.app_entries.clone(), data.app_trash.clone(), data.app_journal.clone());
+	let mut messages = Vec::new();
+	for _ in 0..n {
+		let Some(operation) = journal.pop() else { break };
+		let quand = date::format_date(operation.time);
+
+		let conflit = |titre: &str| eprintln!("{}", data.app_locale.err_undo_conflict
+									.replace("{1}", &operation.op)
+									.replace("{2}", &quand)
+									.replace("{3}", titre));
+
+		// Retirer ce que l'opération a ajouté (entrées ou corbeille)...
+		// Les copies du journal ne gardent que l'empreinte des pièces jointes
+		let mut remplacees = Vec::new();
+		for ajoutee in &operation.added {
+			let liste = if ajoutee.get(entry::DELETED).is_some() { &mut corbeille } else { &mut entries };
+			let condensee = journal::condense(ajoutee);
+			let Some(i) = liste.iter().position(|e| journal::condense(e).same_content(&condensee)) else {
+				// L'entrée a changé depuis, hors journal: l'annulation la ferait disparaître
+				conflit(&ajoutee.title);
+				return Outcome::Failed;
+			};
+			remplacees.push(liste.remove(i));
+		}
+		// ... puis remettre ce qu'elle a retiré, avec l'usage et les pièces jointes de la version remplacée
+		for retiree in &operation.removed {
+			let mut restauree = retiree.clone();
+			if let Some(actuelle) = remplacees.iter().find(|e| e.title == retiree.title) {
+				restauree.keep_usage(actuelle);
+			}
+			if !journal::expand(&mut restauree, &remplacees) {
+				conflit(&retiree.title);
+				return Outcome::Failed;
+			}
+			let liste = if restauree.get(entry::DELETED).is_some() { &mut corbeille } else { &mut entries };
+			liste.push(restauree);
+		}
+
+		let concernees = if operation.added.is_empty() { &operation.removed } else { &operation.added };
+		messages.push(data.app_locale.undo_success
+							.replace("{1}", &operation.op)
+							.replace("{2}", &quand)
+							.replace("{3}", &titres(concernees)));
+	}
+	for m in messages { println!("{m}"); }
+	(data.app_entries, data.app_trash, data.app_journal) = (entries, corbeille, journal);
+	return Outcome::Changed;
+}
+
+/// Affiche le journal des opérations, sans les mots de passe.
+pub fn show_log(data: &AppData) {
+	println!("----------------");
+	if data.app_journal.is_empty() {
+		println!("{}", data.app_locale.log_empty);
+	}
+	for operation in &data.app_journal {
+		println!("{:3}: {} {}", operation.seq, date::format_date(operation.time), operation.op);
+		for e in &operation.removed { println!("       - {}", e.title); }
+		for e in &operation.added { println!("       + {}", e.title); }
+	}
+	println!("----------------");
+}
+
+/// Lit un mot de passe masqué au terminal, en ASCII; avertit si des caractères ont été ignorés.
+pub fn read_pw(message: &str, loc: &LangStrings) -> String {
+	let (pw, ignores) = get_pw!(message);
+	if ignores { eprintln!("{}", loc.warn_pw_ascii); }
+	return pw;
+}
+
+/// Lit le mot de passe d'une nouvelle entrée: sur l'entrée standard (première ligne),
+/// ou au clavier, masqué et saisi deux fois. 'texte': secret d'un type autre qu'identifiant
+/// (contenu d'une note, NIP...), saisi avec ses accents. Affiche l'erreur et retourne None s'il est invalide.
+pub fn read_new_pw(stdin: bool, texte: bool, data: &AppData) -> Option<String> {
+	let saisie = |message: &str| if texte { get_pw::get_secret(message) } else { read_pw(message, &data.app_locale) };
+	let pw = if stdin {
+		let mut ligne = String::new();
+		if io::stdin().read_line(&mut ligne).is_err() { ligne.clear(); }
+		ligne.trim_end_matches(['\n', '\r']).to_string()
+	}
+	else {
+		let pw = saisie(data.app_locale.add_pw_prompt);
+		if !pw.is_empty() && saisie(data.app_locale.add_pw_confirm) != pw {
+			eprintln!("{}", data.app_locale.err_pw_mismatch);
+			return None;
+		}
+		pw
+	};
+	if pw.is_empty() {
+		eprintln!("{}", data.app_locale.err_pw_empty);
+		return None;
+	}
+	Some(pw)
+}
+
+// Clé de comparaison des titres: sans égard à la casse, aux accents ni aux espaces autour.
+fn cle_titre(titre: &str) -> String {
+	search::normalize(titre.trim(), false)
+}
+
+/// Premier titre libre de la forme 'titre (2)', 'titre (3)'...
+fn titre_libre(titre: &str, list: &[Entry]) -> String {
+	(2..)
+		.map(|n| format!("{} ({n})", titre.trim()))
+		.find(|t| !list.iter().any(|e| cle_titre(&e.title) == cle_titre(t)))
+		.unwrap_or_default()
+}
+
+/// Ajoute une entrée. Si une entrée de même titre existe déjà, offre de la mettre à jour,
+/// d'ajouter quand même sous un autre titre ou d'annuler. '--update' met à jour sans demander;
+/// '--yes' seul refuse plutôt que de remplacer un mot de passe sans que l'usager l'ait demandé.
+/// Retourne le titre de l'entrée enregistrée, ou None si l'usager annule ou si le titre
+/// ou le mot de passe ne peut être enregistré.
+pub fn add_entry(mut nouvelle: Entry, data: &mut AppData) -> Option<String> {
+	if !entry::is_storable(&nouvelle.title) || !entry::is_storable(&nouvelle.pw) {
+		eprintln!("{}", data.app_locale.err_separator);
+		return None;
+	}
+	classe(&mut nouvelle, &data.app_filters);
+	nouvelle.touch_created();
+	nouvelle.touch_modified();
+	if let Some(jour) = data.app_expiry.expires { nouvelle.set(entry::EXPIRES, &jour.to_string()); }
+	if let Some(jours) = data.app_expiry.max_age { nouvelle.set(entry::MAX_AGE, &jours.to_string()); }
+	let cle = cle_titre(&nouvelle.title);
+	let Some(index) = data.app_entries.iter().position(|e| cle_titre(&e.title) == cle) else {
+		let titre = nouvelle.title.clone();
+		data.app_entries.push(nouvelle);
+		return Some(titre);
+	};
+
+	let autre_titre = titre_libre(&nouvelle.title, &data.app_entries);
+	println!("{}", data.app_locale.dup_exists.replace("{1}", &data.app_entries[index].title));
+	let mut choix = String::from("1");
+	if !data.app_update {
+		if data.app_assume_yes {
+			eprintln!("{}", data.app_locale.dup_refused);
+			return None;
+		}
+		print!("{}", data.app_locale.dup_choice.replace("{1}", &autre_titre));
 		let _ = io::stdout().flush();
+		choix.clear();
+		if io::stdin().read_line(&mut choix).is_err() { choix.clear(); }
+	}
 
-		let index = get_index(trouve.len(), &data.app_locale);
-		if index > 0 {		// Suppression
-			if let Some(&index_a_supprimer) = trouve.get(index - 1) {
-				if let Some((_, del_desc)) = data.app_line_vec
-												.remove(index_a_supprimer)
-												.split_once("∫∆∫") {
-					println!("{}", data.app_locale.del_success.replace("{1}", del_desc));
-					return true;
-				}
-				else { return false; }
+	match choix.trim() {
+		"1" => {
+			let existante = &mut data.app_entries[index];
+			existante.pw = nouvelle.pw;
+			// La mise à jour garde la date de création de l'entrée existante
+			for (cle, valeur) in nouvelle.fields.iter().filter(|(c, _)| c != entry::CREATED) {
+				existante.set(cle, valeur);
+			}
+			println!("{}", data.app_locale.dup_updated.replace("{1}", &existante.title));
+			Some(existante.title.clone())
+		}
+		"2" => {
+			nouvelle.title = autre_titre.clone();
+			data.app_entries.push(nouvelle);
+			Some(autre_titre)
+		}
+		_ => {
+			println!("{}", data.app_locale.op_cancel);
+			None
+		}
+	}
+}
+
+/// Range l'entrée dans le dossier et ajoute les étiquettes données par --folder et --tag.
+fn classe(entry: &mut Entry, filters: &search::Filters) {
+	if let Some(dossier) = &filters.folder {
+		entry.set(entry::FOLDER, dossier);
+	}
+	let mut tags: Vec<String> = entry.tags().into_iter().map(str::to_string).collect();
+	for t in &filters.tags {
+		if !tags.iter().any(|e| search::normalize(e, false) == search::normalize(t, false)) { tags.push(t.clone()); }
+	}
+	entry.set(entry::TAGS, &tags.join(","));
+}
+
+/// Cherche les entrées de même titre et fusionne chaque groupe en une seule entrée.
+/// Si les mots de passe diffèrent, l'usager choisit celui à garder.
+pub fn dedupe(data: &mut AppData) -> Outcome {
+	// Groupes d'index d'entrées de même titre, dans l'ordre de la liste
+	let mut groupes: Vec<(String, Vec<usize>)> = Vec::new();
+	for (i, e) in data.app_entries.iter().enumerate() {
+		let cle = cle_titre(&e.title);
+		match groupes.iter_mut().find(|(c, _)| *c == cle) {
+			Some((_, groupe)) => groupe.push(i),
+			None => groupes.push((cle, vec![i])),
+		}
+	}
+	groupes.retain(|(_, groupe)| groupe.len() > 1);
+	if groupes.is_empty() {
+		println!("{}", data.app_locale.dedupe_none);
+		return Outcome::Unchanged;
+	}
+
+	let mut a_retirer = Vec::new();
+	for (_, groupe) in &groupes {
+		let titre = data.app_entries[groupe[0]].title.clone();
+		println!("----------------");
+		println!("{}", data.app_locale.dedupe_group
+							.replace("{1}", groupe.len().to_string().as_str())
+							.replace("{2}", &titre));
+		for (x, &i) in groupe.iter().enumerate() {
+			affiche_ligne(x + 1, &data.app_entries[i], data.app_show_pw);
+		}
+
+		let pw = &data.app_entries[groupe[0]].pw;
+		let position = if groupe.iter().all(|&i| data.app_entries[i].pw == *pw) {
+			if !confirm(data.app_locale.dedupe_confirm, data) { continue; }
+			1
+		}
+		else {
+			match select(data.app_locale.dedupe_keep, groupe, &data.app_entries, data) {
+				Ok(p) => p,
+				Err(_) => continue,
+			}
+		};
+
+		let garde = groupe[position - 1];
+		for &i in groupe.iter().filter(|&&i| i != garde) {
+			let doublon = data.app_entries[i].clone();
+			data.app_entries[garde].merge_from(&doublon);
+			a_retirer.push(i);
+		}
+		println!("{}", data.app_locale.dedupe_merged
+							.replace("{1}", &titre)
+							.replace("{2}", groupe.len().to_string().as_str()));
+	}
+	println!("----------------");
+	if a_retirer.is_empty() { return Outcome::Unchanged; }
+
+	a_retirer.sort_unstable_by(|a, b| b.cmp(a));
+	for index in a_retirer {
+		data.app_entries.remove(index);
+	}
+	return Outcome::Changed;
+}
+
+// Lit une ligne au clavier, sans les espaces autour.
+fn read_field(prompt: &str) -> String {
+	print!("{prompt}");
+	let _ = io::stdout().flush();
+	let mut input = String::new();
+	if io::stdin().read_line(&mut input).is_err() { input.clear(); }
+	input.trim().to_string()
+}
+
+/// Assistant de création: demande le type, chaque champ, le mot de passe (généré ou saisi),
+/// montre un résumé puis enregistre l'entrée après confirmation.
+/// Retourne le titre de l'entrée enregistrée, ou None si l'usager annule ou si le titre
+/// ou le mot de passe ne peut être enregistré.
+pub fn wizard(entry_type: Option<&str>, data: &mut AppData) -> Option<String> {
+	let loc = &data.app_locale;
+	let modele = match entry_type.and_then(template::find) {
+		Some(m) => m,
+		None => loop {
+			let nom = read_field(&loc.wiz_type.replace("{1}", &template::names().join(", ")));
+			if nom.is_empty() { break &template::TEMPLATES[0]; }
+			match template::find(&nom) {
+				Some(m) => break m,
+				None => println!("{}", loc.err_bad_type.replace("{1}", &nom).replace("{2}", &template::names().join(", "))),
+			}
+		},
+	};
+
+	let mut titre = read_field(loc.wiz_title);
+	while titre.is_empty() {
+		titre = read_field(loc.wiz_title);
+	}
+	let mut entry = template::new_entry(&titre, "", modele);
+	if modele.name == template::LOGIN {
+		entry.set(entry::USER, &read_field(loc.wiz_user));
+		entry.set(entry::URL, &read_field(loc.wiz_url));
+	}
+	// Champs propres au type: les secrets sont saisis masqués
+	for c in modele.fields {
+		let valeur = if c.secret { get_pw::get_secret(&loc.wiz_secret.replace("{1}", c.key)) } else { read_field(&format!("{}: ", c.key)) };
+		entry.set(c.key, &valeur);
+	}
+	if modele.name == template::LOGIN {
+		let secret = read_pw(loc.wiz_otp, loc);
+		if secret.is_empty() || otp::Otp::parse(&secret).is_some() { entry.set(entry::OTP, &secret); }
+		else { println!("{}", loc.err_otp_invalid.replace("{1}", &entry.title)); }
+	}
+	entry.set(entry::NOTES, &read_field(loc.wiz_notes));
+	// Dossier et étiquettes donnés par --folder/--tag ne sont pas redemandés
+	if data.app_filters.folder.is_none() {
+		entry.set(entry::FOLDER, &entry::clean_folder(&read_field(loc.wiz_folder)));
+	}
+	if data.app_filters.tags.is_empty() {
+		let tags: Vec<String> = read_field(loc.wiz_tags).split(',')
+			.map(|t| t.trim().to_string())
+			.filter(|t| !t.is_empty())
+			.collect();
+		entry.set(entry::TAGS, &tags.join(","));
+	}
+	classe(&mut entry, &data.app_filters);
+	// Échéance: une date, ou un nombre de jours (âge maximal); --expires/--max-age ne sont pas redemandés
+	if data.app_expiry == Expiry::default() {
+		loop {
+			let reponse = read_field(loc.wiz_expiry);
+			if reponse.is_empty() { break; }
+			if let Ok(jours) = reponse.parse::<u64>() {
+				entry.set(entry::MAX_AGE, &jours.to_string());
+				break;
+			}
+			if let Some(jour) = date::parse_day(&reponse) {
+				entry.set(entry::EXPIRES, &jour.to_string());
+				break;
+			}
+			println!("{}", loc.err_bad_date.replace("{1}", &reponse));
+		}
+	}
+
+	// Un mot de passe généré est montré en clair, comme avec -n
+	let mut genere = false;
+	match modele.pw_label {
+		Some(_) if modele.name == template::LOGIN => {
+			genere = read_field(&loc.wiz_pw_choice.replace("{1}", data.app_pw_len.to_string().as_str())) != "2";
+			entry.pw = if genere { generator::gen_pass(data.app_pw_len) } else { read_new_pw(false, false, data)? };
+		}
+		Some(label) => { entry.pw = get_pw::get_secret(&loc.wiz_secret.replace("{1}", label)); }
+		None => { }
+	}
+
+	println!("----------------");
+	println!("{}", loc.wiz_summary);
+	println!("    title: {}", entry.title);
+	// Champs descriptifs, puis ceux du type, le mot de passe et le secret 2FA, masqués comme à l'affichage
+	for (cle, valeur) in entry.fields.iter()
+		.filter(|(c, _)| c != entry::TYPE && c != entry::OTP && !template::is_field(c)) {
+		println!("    {cle}: {valeur}");
+	}
+	for (nom, valeur, secret) in template::display_fields(&entry) {
+		// Un mot de passe généré est montré en clair, comme avec -n
+		let clair = !secret || data.app_show_pw || (genere && modele.pw_label == Some(nom));
+		println!("    {nom}: {}", if clair { valeur } else { MASQUE });
+	}
+	println!("    type: {}", modele.name);
+	println!("----------------");
+	if !confirm(loc.wiz_confirm, data) {
+		println!("{}", loc.op_cancel);
+		return None;
+	}
+	add_entry(entry, data)
+}
+
+/// Code 2FA courant de l'entrée et, pour TOTP, les secondes restantes.
+/// Un code HOTP fait avancer le compteur enregistré: il ne resservira pas.
+fn otp_code(entry: &mut Entry) -> Option<(String, Option<u64>)> {
+	let source = entry.get(entry::OTP)?.to_string();
+	let code = otp::Otp::parse(&source)?;
+	let (valeur, reste) = code.code(date::now());
+	if let otp::Kind::Hotp { counter } = code.kind {
+		entry.set(entry::OTP, &otp::with_counter(&source, counter + 1));
+	}
+	Some((valeur, reste))
+}
+
+/// Affiche le code 2FA d'une entrée, et le copie si un presse-papier existe.
+pub fn show_otp(query: &Query, data: &mut AppData) -> Outcome {
+	let trouve: Vec<usize> = search::rank(query, &data.app_entries).into_iter()
+		.filter(|&i| data.app_entries[i].get(entry::OTP).is_some())
+		.collect();
+	if trouve.is_empty() {
+		println!("{}", data.app_locale.otp_none.replace("{1}", query.source()));
+		return Outcome::NotFound;
+	}
+
+	// Une seule entrée avec un code: inutile de demander
+	let position = if trouve.len() == 1 && data.app_selector == Selector::Interactive { 1 }
+	else {
+		println!("----------------");
+		for (x, &i) in trouve.iter().enumerate() {
+			println!("{:3}: {}", x + 1, data.app_entries[i].title);
+		}
+		println!("----------------");
+		match select(data.app_locale.otp_choose, &trouve, &data.app_entries, data) {
+			Ok(p) => p,
+			Err(outcome) => return outcome,
+		}
+	};
+
+	let entry = &mut data.app_entries[trouve[position - 1]];
+	let Some((code, reste)) = otp_code(entry) else {
+		eprintln!("{}", data.app_locale.err_otp_invalid.replace("{1}", &entry.title));
+		return Outcome::NotFound;
+	};
+	let message = match reste {
+		Some(secondes) => data.app_locale.otp_code.replace("{3}", secondes.to_string().as_str()),
+		// Le compteur affiché est celui du code; le suivant est déjà enregistré
+		None => {
+			let compteur = entry.get(entry::OTP).and_then(otp::Otp::parse).map_or(0, |o| match o.kind {
+				otp::Kind::Hotp { counter } => counter.saturating_sub(1),
+				otp::Kind::Totp { .. } => 0,
+			});
+			data.app_locale.otp_code_hotp.replace("{3}", compteur.to_string().as_str())
+		}
+	};
+	let message = message.replace("{1}", &entry.title).replace("{2}", &code);
+	if command_exist("pbcopy", &data.app_locale) {
+		clipboard::send_to_clipboard(&code);
+		println!("{message} ==> Clipboard");
+	}
+	else { println!("{message}"); }
+
+	entry.touch_used();
+	return Outcome::Changed;
+}
+
+/// Associe (ou retire) le secret 2FA d'une entrée. Le secret est saisi masqué,
+/// ou lu sur l'entrée standard si elle n'est pas un terminal.
+pub fn set_otp(query: &Query, data: &mut AppData) -> Outcome {
+	println!("----------------");
+	let trouve = scan(query, &data.app_entries, data.app_show_pw);
+	if trouve.is_empty() {
+		println!("\t------ {}", data.app_locale.no_match.replace("{1}", query.source()));
+		println!("----------------");
+		return Outcome::NotFound;
+	}
+	println!("----------------");
+	let position = match select(data.app_locale.otp_to_set, &trouve, &data.app_entries, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+
+	let secret = if get_pw::is_tty(io::stdin().as_raw_fd()) {
+		read_pw(data.app_locale.otp_prompt, &data.app_locale)
+	}
+	else {
+		let mut ligne = String::new();
+		if io::stdin().read_line(&mut ligne).is_err() { ligne.clear(); }
+		ligne.trim().to_string()
+	};
+	let entry = &mut data.app_entries[trouve[position - 1]];
+	if !secret.is_empty() && otp::Otp::parse(&secret).is_none() {
+		eprintln!("{}", data.app_locale.err_otp_invalid.replace("{1}", &entry.title));
+		return Outcome::Failed;
+	}
+	entry.set(entry::OTP, &secret);
+	entry.touch_modified();
+	let message = if secret.is_empty() { data.app_locale.otp_removed } else { data.app_locale.otp_saved };
+	println!("{}", message.replace("{1}", &entry.title));
+	return Outcome::Changed;
+}
+
+/// Fixe un champ personnalisé, descriptif ou du type d'une entrée (numéro d'une carte...).
+/// Sans valeur donnée, elle est saisie au terminal, masquée si le champ est secret,
+/// ou lue sur une ligne de l'entrée standard. Une valeur vide retire le champ.
+pub fn set_field(query: &Query, nom: &str, valeur: Option<&str>, secret: bool, data: &mut AppData) -> Outcome {
+	println!("----------------");
+	let trouve = scan(query, &data.app_entries, data.app_show_pw);
+	if trouve.is_empty() {
+		println!("\t------ {}", data.app_locale.no_match.replace("{1}", query.source()));
+		println!("----------------");
+		return Outcome::NotFound;
+	}
+	println!("----------------");
+	let position = match select(data.app_locale.index_to_field, &trouve, &data.app_entries, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+	let i = trouve[position - 1];
+
+	// Un champ de type n'existe que sur les entrées de ce type, qui décide s'il est secret
+	let mut secret = secret;
+	if template::is_field(nom) {
+		let modele = template::of(&data.app_entries[i]);
+		let Some(champ) = modele.fields.iter().find(|c| c.key == nom) else {
+			eprintln!("{}", data.app_locale.err_field_type.replace("{1}", nom).replace("{2}", modele.name)
+								.replace("{3}", &data.app_entries[i].title));
+			return Outcome::Failed;
+		};
+		secret = champ.secret;
+		if secret && valeur.is_some() { eprintln!("{}", data.app_locale.warn_type_secret_argv); }
+	}
+
+	let valeur = match valeur {
+		Some(v) => v.to_string(),
+		None if get_pw::is_tty(io::stdin().as_raw_fd()) => {
+			let prompt = data.app_locale.field_prompt.replace("{1}", nom);
+			if secret { get_pw::get_secret(&prompt) } else { read_field(&prompt) }
+		}
+		None => {
+			let mut ligne = String::new();
+			if io::stdin().read_line(&mut ligne).is_err() { ligne.clear(); }
+			ligne.trim_end_matches(['\r', '\n']).to_string()
+		}
+	};
+	// Une URL saisie est vérifiée comme celle donnée en argument
+	if nom == entry::URL && !valeur.is_empty() && domain::host(&valeur).is_none() {
+		eprintln!("{}", data.app_locale.err_bad_url.replace("{1}", &valeur));
+		return Outcome::Failed;
+	}
+	let entry = &mut data.app_entries[i];
+	match nom {
+		entry::FOLDER => entry.set(nom, &entry::clean_folder(&valeur)),
+		_ if entry::EDITABLE.contains(&nom) || template::is_field(nom) => entry.set(nom, &valeur),
+		_ => entry.set_custom(nom, &valeur, secret),
+	}
+	entry.touch_modified();
+	let message = if valeur.is_empty() { data.app_locale.field_removed } else { data.app_locale.field_saved };
+	println!("{}", message.replace("{1}", nom).replace("{2}", &entry.title));
+	return Outcome::Changed;
+}
+
+/// Fixe l'échéance d'une entrée selon --expires et --max-age; sans l'un ni l'autre, la retire.
+pub fn set_expiry(query: &Query, data: &mut AppData) -> Outcome {
+	println!("----------------");
+	let trouve = scan(query, &data.app_entries, data.app_show_pw);
+	if trouve.is_empty() {
+		println!("\t------ {}", data.app_locale.no_match.replace("{1}", query.source()));
+		println!("----------------");
+		return Outcome::NotFound;
+	}
+	println!("----------------");
+	let position = match select(data.app_locale.index_to_expire, &trouve, &data.app_entries, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+
+	let entry = &mut data.app_entries[trouve[position - 1]];
+	entry.set(entry::EXPIRES, &data.app_expiry.expires.map(|j| j.to_string()).unwrap_or_default());
+	entry.set(entry::MAX_AGE, &data.app_expiry.max_age.map(|j| j.to_string()).unwrap_or_default());
+	// L'âge maximal se compte depuis la dernière modification: faute de date connue, depuis maintenant
+	if entry.get(entry::MAX_AGE).is_some() && entry.get(entry::MODIFIED).is_none() {
+		entry.touch_modified();
+	}
+	match entry.due() {
+		Some(jour) => println!("{}", data.app_locale.expiry_set.replace("{1}", &entry.title).replace("{2}", &date::format_day(jour))),
+		None => println!("{}", data.app_locale.expiry_removed.replace("{1}", &entry.title)),
+	}
+	return Outcome::Changed;
+}
+
+/// Entrées dont l'échéance tombe d'ici 'jours' jours, ou est déjà dépassée, la plus proche d'abord.
+pub fn due(jours: u64, data: &AppData) -> Outcome {
+	let maintenant = date::now();
+	let limite = maintenant + jours * date::SECS_PER_DAY;
+	let mut trouve: Vec<(u64, &Entry)> = data.app_entries.iter()
+		.filter_map(|e| e.due().map(|d| (d, e)))
+		.filter(|&(d, _)| d <= limite)
+		.collect();
+	trouve.sort_by_key(|&(d, _)| d);
+
+	println!("----------------");
+	if trouve.is_empty() {
+		println!("{}", data.app_locale.due_none.replace("{1}", jours.to_string().as_str()));
+	}
+	for (x, (echeance, entry)) in trouve.iter().enumerate() {
+		let etat = if *echeance <= maintenant {
+			data.app_locale.due_overdue.replace("{1}", ((maintenant - echeance) / date::SECS_PER_DAY).to_string().as_str())
+		}
+		else {
+			data.app_locale.due_soon.replace("{1}", (echeance - maintenant).div_ceil(date::SECS_PER_DAY).to_string().as_str())
+		};
+		println!("{:3}: {}  {} ({etat})", x + 1, date::format_day(*echeance), chemin_titre(entry));
+	}
+	println!("----------------");
+	return Outcome::Unchanged;
+}
+
+/// Rappel d'une ligne, après le déverrouillage, si des entrées ont dépassé leur échéance.
+pub fn warn_overdue(data: &AppData) {
+	let maintenant = date::now();
+	let n = data.app_entries.iter().filter(|e| e.due().is_some_and(|d| d <= maintenant)).count();
+	if n > 0 {
+		eprintln!("{}", data.app_locale.warn_overdue.replace("{1}", n.to_string().as_str()));
+	}
+}
+
+// Taille d'une pièce jointe, en octets, d'après son contenu en base64.
+fn taille_jointe(contenu: &str) -> usize {
+	base64::decode(contenu).map_or(0, |octets| octets.len())
+}
+
+// Entrées du motif qui ont la pièce jointe 'nom', les plus pertinentes d'abord.
+fn avec_jointe(query: &Query, nom: &str, data: &AppData) -> Vec<usize> {
+	search::rank(query, &data.app_entries).into_iter()
+		.filter(|&i| data.app_entries[i].get(&format!("{}{nom}", entry::ATTACH)).is_some())
+		.collect()
+}
+
+/// Joint un fichier à une entrée, chiffré avec le reste du fichier mdp.
+/// Une pièce jointe du même nom est remplacée après confirmation.
+pub fn attach(query: &Query, fichier: &Path, data: &mut AppData) -> Outcome {
+	let loc = &data.app_locale;
+	let octets = match fs::read(fichier) {
+		Ok(o) => o,
+		Err(e) => {
+			eprintln!("{}", loc.err_attach_read.replace("{1}", &fichier.to_string_lossy()).replace("{2}", &e.to_string()));
+			return Outcome::Failed;
+		}
+	};
+	if octets.len() > MAX_ATTACHMENT {
+		eprintln!("{}", loc.err_attach_big.replace("{1}", &fichier.to_string_lossy()).replace("{2}", MAX_ATTACHMENT.to_string().as_str()));
+		return Outcome::Failed;
+	}
+	let nom = entry::clean_attachment_name(&fichier.file_name().unwrap_or_default().to_string_lossy());
+
+	println!("----------------");
+	let trouve = scan(query, &data.app_entries, data.app_show_pw);
+	if trouve.is_empty() {
+		println!("\t------ {}", loc.no_match.replace("{1}", query.source()));
+		println!("----------------");
+		return Outcome::NotFound;
+	}
+	println!("----------------");
+	let position = match select(loc.index_to_attach, &trouve, &data.app_entries, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+
+	let i = trouve[position - 1];
+	let jointes = data.app_entries[i].attachments();
+	let total: usize = jointes.iter().filter(|(n, _)| *n != nom).map(|(_, c)| taille_jointe(c)).sum();
+	if total + octets.len() > MAX_ATTACHMENTS {
+		eprintln!("{}", loc.err_attach_total.replace("{1}", &data.app_entries[i].title).replace("{2}", MAX_ATTACHMENTS.to_string().as_str()));
+		return Outcome::Failed;
+	}
+	if jointes.iter().any(|(n, _)| *n == nom) && !confirm(&loc.attach_replace.replace("{1}", &nom), data) {
+		println!("{}", loc.op_cancel);
+		return Outcome::Unchanged;
+	}
+
+	let entry = &mut data.app_entries[i];
+	entry.set(&format!("{}{nom}", entry::ATTACH), &base64::encode(&octets));
+	entry.touch_modified();
+	println!("{}", data.app_locale.attach_saved.replace("{1}", &nom).replace("{2}", &entry.title)
+							.replace("{3}", octets.len().to_string().as_str()));
+	return Outcome::Changed;
+}
+
+/// Retire une pièce jointe d'une entrée.
+pub fn detach(query: &Query, nom: &str, data: &mut AppData) -> Outcome {
+	println!("----------------");
+	let trouve = avec_jointe(query, nom, data);
+	if trouve.is_empty() {
+		println!("{}", data.app_locale.attach_none_named.replace("{1}", nom).replace("{2}", query.source()));
+		println!("----------------");
+		return Outcome::NotFound;
+	}
+	for (x, &i) in trouve.iter().enumerate() {
+		affiche_ligne(x + 1, &data.app_entries[i], data.app_show_pw);
+	}
+	println!("----------------");
+	let position = match select(data.app_locale.index_to_detach, &trouve, &data.app_entries, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+
+	let entry = &mut data.app_entries[trouve[position - 1]];
+	entry.set(&format!("{}{nom}", entry::ATTACH), "");
+	entry.touch_modified();
+	println!("{}", data.app_locale.detach_done.replace("{1}", nom).replace("{2}", &entry.title));
+	return Outcome::Changed;
+}
+
+/// Pièces jointes des entrées du motif (toutes les entrées pour un motif vide), avec leur taille.
+pub fn list_attachments(query: &Query, data: &AppData) -> Outcome {
+	let trouve: Vec<&Entry> = search::rank(query, &data.app_entries).into_iter()
+		.map(|i| &data.app_entries[i])
+		.filter(|e| !e.attachments().is_empty())
+		.collect();
+
+	println!("----------------");
+	if trouve.is_empty() {
+		println!("{}", data.app_locale.attach_empty);
+	}
+	for (x, entry) in trouve.iter().enumerate() {
+		println!("{:3}: {}", x + 1, chemin_titre(entry));
+		for (nom, contenu) in entry.attachments() {
+			println!("         {nom} ({} {})", taille_jointe(contenu), data.app_locale.bytes);
+		}
+	}
+	println!("----------------");
+	if trouve.is_empty() { return Outcome::NotFound; }
+	return Outcome::Unchanged;
+}
+
+/// Extrait une pièce jointe vers un fichier (permissions 0600) ou, sans destination, vers la sortie standard.
+pub fn extract(query: &Query, nom: &str, destination: Option<&Path>, data: &mut AppData) -> Outcome {
+	let trouve = avec_jointe(query, nom, data);
+	if trouve.is_empty() {
+		eprintln!("{}", data.app_locale.attach_none_named.replace("{1}", nom).replace("{2}", query.source()));
+		return Outcome::NotFound;
+	}
+	let position = match select_quiet(query, &trouve, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+
+	let i = trouve[position - 1];
+	let contenu = data.app_entries[i].get(&format!("{}{nom}", entry::ATTACH)).unwrap_or_default();
+	let Some(octets) = base64::decode(contenu) else {
+		eprintln!("{}", data.app_locale.err_attach_corrupt.replace("{1}", nom));
+		return Outcome::Failed;
+	};
+
+	let resultat = match destination {
+		Some(chemin) => {
+			if chemin.exists() && !confirm(&data.app_locale.extract_overwrite.replace("{1}", &chemin.to_string_lossy()), data) {
+				println!("{}", data.app_locale.op_cancel);
+				return Outcome::Unchanged;
 			}
-			else { return false; }
+			// Le mode ne s'applique qu'à la création: un fichier existant est aussi ramené à 0600
+			fs::OpenOptions::new().write(true).create(true).truncate(true).mode(0o600).open(chemin)
+				.and_then(|mut f| {
+					f.set_permissions(fs::Permissions::from_mode(0o600))?;
+					f.write_all(&octets)
+				})
+		}
+		None => {
+			let mut sortie = io::stdout();
+			sortie.write_all(&octets).and_then(|_| sortie.flush())
+		}
+	};
+	if let Err(e) = resultat {
+		eprintln!("{}", data.app_locale.err_extract.replace("{1}", nom).replace("{2}", &e.to_string()));
+		return Outcome::Failed;
+	}
+	if let Some(chemin) = destination {
+		println!("{}", data.app_locale.extract_done.replace("{1}", nom).replace("{2}", &chemin.to_string_lossy())
+							.replace("{3}", octets.len().to_string().as_str()));
+	}
+
+	data.app_entries[i].touch_used();
+	return Outcome::Changed;
+}
+
+/// Importe des comptes 2FA lus sur l'entrée standard, une URI par ligne.
+/// Au terminal, une ligne vide termine la saisie.
+pub fn import_otp(data: &mut AppData) -> Outcome {
+	let terminal = get_pw::is_tty(io::stdin().as_raw_fd());
+	if terminal { println!("{}", data.app_locale.import_prompt); }
+
+	let mut importes = 0;
+	for (n, ligne) in io::stdin().lines().enumerate() {
+		let Ok(ligne) = ligne else { break };
+		if ligne.trim().is_empty() {
+			if terminal { break; }
+			continue;
+		}
+		let Some(comptes) = otp::parse_import(&ligne) else {
+			eprintln!("{}", data.app_locale.err_import_line.replace("{1}", (n + 1).to_string().as_str()));
+			continue;
+		};
+		for compte in comptes {
+			import_compte(compte, data);
+			importes += 1;
 		}
-		else { return false; }
 	}
-}
\ No newline at end of file
+	println!("{}", data.app_locale.import_done.replace("{1}", importes.to_string().as_str()));
+	if importes == 0 { return Outcome::Unchanged; }
+	return Outcome::Changed;
+}
+
+// Un compte importé rejoint l'entrée du même service (et du même compte) qui n'a pas encore
+// de code 2FA; sinon il devient une nouvelle entrée.
+fn import_compte(compte: otp::Imported, data: &mut AppData) {
+	let titre = [&compte.issuer, &compte.account].into_iter()
+		.find(|t| !t.is_empty() && entry::is_storable(t))
+		.map_or("OTP".to_string(), |t| t.to_string());
+	let cle = cle_titre(&titre);
+	let existante = data.app_entries.iter().position(|e| {
+		cle_titre(&e.title) == cle && e.get(entry::OTP).is_none()
+			&& e.get(entry::USER).is_none_or(|u| compte.account.is_empty() || u.eq_ignore_ascii_case(&compte.account))
+	});
+
+	match existante {
+		Some(i) => {
+			data.app_entries[i].set(entry::OTP, &compte.uri);
+			data.app_entries[i].touch_modified();
+			println!("{}", data.app_locale.import_attached.replace("{1}", &data.app_entries[i].title));
+		}
+		None => {
+			let titre = if data.app_entries.iter().any(|e| cle_titre(&e.title) == cle) {
+				titre_libre(&titre, &data.app_entries)
+			}
+			else { titre };
+			let mut nouvelle = Entry::new(&titre, "");
+			nouvelle.set(entry::USER, &compte.account);
+			nouvelle.set(entry::OTP, &compte.uri);
+			nouvelle.touch_created();
+			nouvelle.touch_modified();
+			println!("{}", data.app_locale.import_new.replace("{1}", &titre).replace("{2}", &compte.account));
+			data.app_entries.push(nouvelle);
+		}
+	}
+}
+
+/// URI otpauth:// de l'entrée; un secret base32 seul devient une URI TOTP au nom de l'entrée.
+fn otp_uri(entry: &Entry) -> Option<String> {
+	let source = entry.get(entry::OTP)?;
+	if source.starts_with("otpauth://") { return Some(source.to_string()); }
+	let secret: String = source.chars().filter(|c| !c.is_whitespace()).collect();
+	Some(format!("otpauth://totp/{}?secret={secret}", otp::percent_encode(&entry.title)))
+}
+
+// Caractères réservés d'une configuration 'WIFI:'
+fn wifi_escape(texte: &str) -> String {
+	texte.chars().fold(String::new(), |mut s, c| {
+		if "\\;,:\"".contains(c) { s.push('\\'); }
+		s.push(c);
+		s
+	})
+}
+
+/// Texte à mettre en code QR: le champ demandé, sinon la configuration d'un réseau Wi-Fi,
+/// l'URI 2FA d'une entrée sans mot de passe, ou le secret principal.
+fn qr_payload(entry: &Entry, field: Option<&str>) -> Option<String> {
+	let texte = match field {
+		Some(entry::OTP) => otp_uri(entry),
+		Some("password") => Some(entry.pw.clone()),
+		Some(champ) => entry.get(champ).or_else(|| entry.custom(champ)).map(str::to_string),
+		None if template::of(entry).name == "wifi" => {
+			let securite = entry.get("security").unwrap_or_default().to_uppercase();
+			let t = if securite.contains("WEP") { "WEP" }
+				else if entry.pw.is_empty() || matches!(securite.as_str(), "NONE" | "OPEN" | "NOPASS") { "nopass" }
+				else { "WPA" };
+			let ssid = entry.get("ssid").unwrap_or(&entry.title);
+			Some(format!("WIFI:T:{t};S:{};P:{};;", wifi_escape(ssid), wifi_escape(&entry.pw)))
+		}
+		None if entry.pw.is_empty() && entry.get(entry::OTP).is_some() => otp_uri(entry),
+		None => Some(template::main_secret(entry).to_string()),
+	};
+	texte.filter(|t| !t.is_empty())
+}
+
+/// Affiche le secret d'une entrée en code QR, pour le passer à un téléphone.
+pub fn show_qr(query: &Query, field: Option<&str>, data: &mut AppData) -> Outcome {
+	println!("----------------");
+	let trouve = scan(query, &data.app_entries, data.app_show_pw);
+	if trouve.is_empty() {
+		println!("\t------ {}", data.app_locale.no_match.replace("{1}", query.source()));
+		println!("----------------");
+		return Outcome::NotFound;
+	}
+	println!("----------------");
+	let position = match select(data.app_locale.index_to_qr, &trouve, &data.app_entries, data) {
+		Ok(p) => p,
+		Err(outcome) => return outcome,
+	};
+
+	let entry = &mut data.app_entries[trouve[position - 1]];
+	let Some(texte) = qr_payload(entry, field) else {
+		eprintln!("{}", data.app_locale.err_no_field.replace("{1}", &entry.title).replace("{2}", field.unwrap_or("password")));
+		return Outcome::NotFound;
+	};
+	let Some(modules) = qr::encode(texte.as_bytes()) else {
+		eprintln!("{}", data.app_locale.err_qr_too_long.replace("{1}", texte.len().to_string().as_str()));
+		return Outcome::Failed;
+	};
+	println!("{}", entry.title);
+	print!("{}", qr::to_terminal(&modules));
+
+	entry.touch_used();
+	return Outcome::Changed;
+}
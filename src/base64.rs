@@ -0,0 +1,71 @@
+// base64.rs
+
+// Base64 (RFC 4648), alphabet standard et variante URL ('-' et '_'), sans dépendance.
+
+fn valeur(c: u8) -> Option<u32> {
+	match c {
+		b'A'..=b'Z' => Some((c - b'A') as u32),
+		b'a'..=b'z' => Some((c - b'a') as u32 + 26),
+		b'0'..=b'9' => Some((c - b'0') as u32 + 52),
+		b'+' | b'-' => Some(62),
+		b'/' | b'_' => Some(63),
+		_ => None,
+	}
+}
+
+/// Décode du base64, standard ou URL, avec ou sans remplissage '='. Les blancs sont ignorés.
+pub fn decode(texte: &str) -> Option<Vec<u8>> {
+	let mut octets = Vec::with_capacity(texte.len() * 3 / 4);
+	let mut tampon: u32 = 0;
+	let mut bits = 0;
+	for c in texte.bytes().filter(|c| !c.is_ascii_whitespace() && *c != b'=') {
+		tampon = (tampon << 6) | valeur(c)?;
+		bits += 6;
+		if bits >= 8 {
+			bits -= 8;
+			octets.push((tampon >> bits) as u8);
+		}
+	}
+	Some(octets)
+}
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode en base64 standard, avec remplissage '='.
+pub fn encode(octets: &[u8]) -> String {
+	let mut texte = String::with_capacity(octets.len().div_ceil(3) * 4);
+	for bloc in octets.chunks(3) {
+		let n = bloc.iter().enumerate().fold(0u32, |n, (i, &o)| n | (o as u32) << (16 - 8 * i));
+		for i in 0..4 {
+			if i <= bloc.len() { texte.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char); }
+			else { texte.push('='); }
+		}
+	}
+	texte
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn vecteurs_rfc_4648() {
+		let vecteurs = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foob", "Zm9vYg=="),
+						("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy")];
+		for (clair, code) in vecteurs {
+			assert_eq!(encode(clair.as_bytes()), code);
+			assert_eq!(decode(code).as_deref(), Some(clair.as_bytes()));
+		}
+	}
+
+	#[test]
+	fn variantes_et_erreurs() {
+		// Variante URL, sans remplissage, avec blancs
+		assert_eq!(decode("-_8").as_deref(), Some(&[0xfb, 0xff][..]));
+		assert_eq!(decode("+/8=").as_deref(), Some(&[0xfb, 0xff][..]));
+		assert_eq!(decode("Zm9v\nYmFy").as_deref(), Some(&b"foobar"[..]));
+		assert_eq!(decode("Zm9v!"), None);
+		let octets: Vec<u8> = (0..=255).cycle().take(1000).collect();
+		assert_eq!(decode(&encode(&octets)), Some(octets));
+	}
+}
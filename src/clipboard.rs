@@ -13,10 +13,7 @@ pub fn send_to_clipboard(text: &str) -> bool {
 	// 2. Tente d'écrire le texte dans le pipe
 	let success = if let Some(mut stdin) = child.stdin.take() {
 		// Écrit le texte dans le pipe
-		match stdin.write_all(text.as_bytes()) {
-			Ok(_) => true,
-			Err(_) => false,
-		}
+		stdin.write_all(text.as_bytes()).is_ok()
 		// Le pipe est fermé ici lorsque 'stdin' sort du scope
 	}
 	else { false };		// Impossible d'obtenir le pipe d'entrée
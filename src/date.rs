@@ -0,0 +1,78 @@
+// date.rs
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// --- Heure locale via la libc ---
+
+#[repr(C)]
+struct Tm {
+	tm_sec: i32,
+	tm_min: i32,
+	tm_hour: i32,
+	tm_mday: i32,
+	tm_mon: i32,
+	tm_year: i32,
+	tm_wday: i32,
+	tm_yday: i32,
+	tm_isdst: i32,
+	tm_gmtoff: i64,
+	tm_zone: *const i8,
+}
+
+unsafe extern "C" {
+	fn localtime_r(timep: *const i64, result: *mut Tm) -> *mut Tm;
+	fn mktime(tm: *mut Tm) -> i64;
+}
+
+pub const SECS_PER_DAY: u64 = 86400;
+
+/// Secondes écoulées depuis l'époque UNIX.
+pub fn now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn local(secs: u64) -> Option<Tm> {
+	let t = secs as i64;
+	let mut tm: Tm = unsafe { std::mem::zeroed() };
+	if unsafe { localtime_r(&t, &mut tm) }.is_null() {
+		return None;
+	}
+	Some(tm)
+}
+
+/// Date et heure locales 'AAAA-MM-JJ HH:MM'.
+pub fn format_date(secs: u64) -> String {
+	match local(secs) {
+		Some(tm) => format!("{:04}-{:02}-{:02} {:02}:{:02}",
+						tm.tm_year + 1900, tm.tm_mon + 1, tm.tm_mday, tm.tm_hour, tm.tm_min),
+		None => secs.to_string(),
+	}
+}
+
+/// Date locale 'AAAA-MM-JJ'.
+pub fn format_day(secs: u64) -> String {
+	match local(secs) {
+		Some(tm) => format!("{:04}-{:02}-{:02}", tm.tm_year + 1900, tm.tm_mon + 1, tm.tm_mday),
+		None => secs.to_string(),
+	}
+}
+
+/// Minuit, heure locale, du jour 'AAAA-MM-JJ'. Une date qui n'existe pas (31 février) est refusée.
+pub fn parse_day(texte: &str) -> Option<u64> {
+	let parties: Vec<i32> = texte.trim().split('-').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+	let [annee, mois, jour] = parties[..] else { return None };
+	if annee < 1970 || !(1..=12).contains(&mois) || !(1..=31).contains(&jour) {
+		return None;
+	}
+	let mut tm: Tm = unsafe { std::mem::zeroed() };
+	tm.tm_year = annee - 1900;
+	tm.tm_mon = mois - 1;
+	tm.tm_mday = jour;
+	tm.tm_isdst = -1;
+	let t = unsafe { mktime(&mut tm) };
+	// mktime normalise les dates impossibles (31 février => 3 mars)
+	if t < 0 || tm.tm_mon != mois - 1 || tm.tm_mday != jour {
+		return None;
+	}
+	Some(t as u64)
+}
@@ -0,0 +1,185 @@
+// domain.rs
+
+// Domaines des URL: hôte, domaine enregistrable (selon une partie de la liste des suffixes publics,
+// publicsuffix.org) et degré de correspondance entre deux hôtes.
+
+/// Degré de correspondance d'une entrée avec l'URL cherchée, du plus large au plus strict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Match {
+	#[default]
+	Domain,		// Même domaine enregistrable: 'mail.example.co.uk' et 'login.example.co.uk'
+	Subdomain,	// Même hôte, ou hôte parent: 'example.co.uk' pour 'login.example.co.uk'
+	Exact,		// Même hôte
+}
+
+impl Match {
+	pub const NAMES: [&'static str; 3] = ["domain", "subdomain", "exact"];
+
+	pub fn from_name(nom: &str) -> Option<Self> {
+		match nom {
+			"domain" => Some(Match::Domain),
+			"subdomain" => Some(Match::Subdomain),
+			"exact" => Some(Match::Exact),
+			_ => None,
+		}
+	}
+}
+
+// Suffixes publics courants. '*' remplace une étiquette quelconque; '!' marque une exception
+// à une règle '*'. Un domaine de premier niveau absent de la liste compte pour un suffixe.
+const SUFFIXES: &[&str] = &[
+	// Génériques à plusieurs niveaux
+	"co.com", "us.com", "uk.com", "eu.com", "br.com", "cn.com", "de.com", "jpn.com",
+	// Amériques
+	"qc.ca", "on.ca", "bc.ca", "ab.ca", "mb.ca", "sk.ca", "ns.ca", "nb.ca", "nl.ca", "pe.ca", "gc.ca",
+	"com.mx", "org.mx", "gob.mx", "edu.mx", "net.mx",
+	"com.br", "net.br", "org.br", "gov.br", "edu.br",
+	"com.ar", "org.ar", "gob.ar", "gov.ar", "net.ar",
+	"com.co", "org.co", "gov.co", "net.co", "edu.co",
+	"gob.cl", "com.pe", "gob.pe", "org.pe", "com.ve", "com.uy", "gub.uy", "com.ec", "gob.ec",
+	// Europe
+	"co.uk", "org.uk", "me.uk", "ltd.uk", "plc.uk", "net.uk", "ac.uk", "gov.uk", "nhs.uk", "police.uk", "sch.uk",
+	"gouv.fr", "asso.fr", "com.fr", "nom.fr", "tm.fr",
+	"com.es", "org.es", "gob.es", "nom.es", "edu.es",
+	"co.at", "or.at", "gv.at", "ac.at",
+	"com.pt", "gov.pt", "org.pt", "edu.pt",
+	"com.pl", "net.pl", "org.pl", "gov.pl", "edu.pl",
+	"co.it", "gov.it", "edu.it",
+	"com.gr", "gov.gr", "com.tr", "gov.tr", "org.tr", "edu.tr",
+	"com.ua", "gov.ua", "org.ua", "com.ru", "org.ru", "net.ru",
+	"ac.be", "co.nl", "gv.nl", "co.no", "priv.no",
+	// Asie et Océanie
+	"com.au", "net.au", "org.au", "edu.au", "gov.au", "asn.au", "id.au",
+	"co.nz", "net.nz", "org.nz", "govt.nz", "ac.nz", "school.nz",
+	"co.jp", "ne.jp", "or.jp", "ac.jp", "go.jp", "ed.jp", "gr.jp", "lg.jp",
+	"co.kr", "or.kr", "ne.kr", "go.kr", "ac.kr", "re.kr",
+	"com.cn", "net.cn", "org.cn", "gov.cn", "edu.cn", "ac.cn",
+	"com.hk", "org.hk", "gov.hk", "edu.hk", "net.hk", "idv.hk",
+	"com.tw", "org.tw", "gov.tw", "edu.tw", "idv.tw", "net.tw",
+	"com.sg", "org.sg", "gov.sg", "edu.sg", "net.sg",
+	"co.in", "net.in", "org.in", "gov.in", "ac.in", "res.in", "firm.in", "gen.in", "ind.in",
+	"co.id", "or.id", "go.id", "ac.id", "web.id",
+	"com.my", "org.my", "gov.my", "edu.my", "net.my",
+	"com.ph", "gov.ph", "org.ph", "edu.ph",
+	"co.th", "or.th", "go.th", "ac.th", "in.th",
+	"com.vn", "gov.vn", "edu.vn", "net.vn",
+	"com.pk", "gov.pk", "org.pk", "edu.pk",
+	"co.il", "org.il", "gov.il", "ac.il", "net.il", "muni.il",
+	"com.sa", "gov.sa", "edu.sa", "com.eg", "gov.eg", "edu.eg",
+	"gov.ae", "co.ae",
+	// Afrique
+	"co.za", "org.za", "gov.za", "ac.za", "web.za", "net.za",
+	"co.ke", "or.ke", "go.ke", "ac.ke", "com.ng", "gov.ng", "org.ng", "edu.ng", "co.ma", "gov.ma",
+	// Règles génériques
+	"*.ck", "!www.ck", "*.bd", "*.np", "*.kw", "*.er", "*.fk", "*.mm",
+	// Domaines privés: chaque sous-domaine appartient à un client distinct
+	"github.io", "gitlab.io", "netlify.app", "vercel.app", "pages.dev", "workers.dev", "web.app",
+	"firebaseapp.com", "herokuapp.com", "appspot.com", "azurewebsites.net", "cloudfront.net",
+	"blogspot.com", "wordpress.com", "s3.amazonaws.com", "elasticbeanstalk.com",
+];
+
+/// Hôte d'une URL, en minuscules, sans schéma, identifiants, port ni chemin.
+/// 'https://Bob@Login.Example.co.uk:8443/path?x' => 'login.example.co.uk'. Une URL sans schéma est acceptée.
+pub fn host(url: &str) -> Option<String> {
+	let reste = url.trim();
+	let reste = reste.split_once("://").map_or(reste, |(_, r)| r);
+	let autorite = reste.split(['/', '?', '#']).next().unwrap_or_default();
+	let autorite = autorite.rsplit_once('@').map_or(autorite, |(_, a)| a);
+	let hote = match autorite.strip_prefix('[') {
+		// IPv6 entre crochets
+		Some(v6) => v6.split(']').next().unwrap_or_default(),
+		None => autorite.split(':').next().unwrap_or_default(),
+	};
+	let hote = hote.trim_end_matches('.').to_lowercase();
+	if hote.is_empty() || hote.chars().any(|c| c.is_whitespace()) { return None; }
+	Some(hote)
+}
+
+fn is_ip(hote: &str) -> bool {
+	hote.contains(':') || hote.split('.').all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+// Nombre d'étiquettes du suffixe public de l'hôte.
+fn suffix_len(etiquettes: &[&str]) -> usize {
+	let mut meilleur = 1;		// Règle implicite: le domaine de premier niveau
+	for regle in SUFFIXES {
+		let (exception, regle) = match regle.strip_prefix('!') {
+			Some(r) => (true, r),
+			None => (false, *regle),
+		};
+		let parties: Vec<&str> = regle.split('.').collect();
+		if parties.len() > etiquettes.len() { continue; }
+		let fin = &etiquettes[etiquettes.len() - parties.len()..];
+		if parties.iter().zip(fin).all(|(p, e)| *p == "*" || p == e) {
+			// Une exception l'emporte: son suffixe est la règle sans sa première étiquette
+			if exception { return parties.len() - 1; }
+			meilleur = meilleur.max(parties.len());
+		}
+	}
+	meilleur
+}
+
+/// Domaine enregistrable: le suffixe public et l'étiquette qui le précède.
+/// 'login.example.co.uk' => 'example.co.uk'. Une adresse IP est son propre domaine;
+/// un suffixe public seul ('co.uk') n'en a pas.
+pub fn registrable(hote: &str) -> Option<String> {
+	if is_ip(hote) { return Some(hote.to_string()); }
+	let etiquettes: Vec<&str> = hote.split('.').collect();
+	let n = suffix_len(&etiquettes);
+	if etiquettes.len() <= n { return None; }
+	Some(etiquettes[etiquettes.len() - n - 1..].join("."))
+}
+
+/// Correspondance la plus stricte entre l'hôte cherché et celui d'une entrée, ou None.
+pub fn compare(cherche: &str, hote: &str) -> Option<Match> {
+	if cherche == hote { return Some(Match::Exact); }
+	let domaine = registrable(cherche)?;
+	if registrable(hote).as_deref() != Some(domaine.as_str()) { return None; }
+	if cherche.ends_with(&format!(".{hote}")) { return Some(Match::Subdomain); }
+	Some(Match::Domain)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn hote_des_url() {
+		assert_eq!(host("https://Bob@Login.Example.co.uk:8443/path?x").as_deref(), Some("login.example.co.uk"));
+		assert_eq!(host("example.com/login").as_deref(), Some("example.com"));
+		assert_eq!(host("http://[::1]:8080/").as_deref(), Some("::1"));
+		assert_eq!(host("https://example.com./").as_deref(), Some("example.com"));
+		assert_eq!(host("https:///chemin"), None);
+		assert_eq!(host("   "), None);
+	}
+
+	#[test]
+	fn domaines_enregistrables() {
+		assert_eq!(registrable("login.example.co.uk").as_deref(), Some("example.co.uk"));
+		assert_eq!(registrable("example.co.uk").as_deref(), Some("example.co.uk"));
+		assert_eq!(registrable("a.b.example.com").as_deref(), Some("example.com"));
+		assert_eq!(registrable("www.revenu.gouv.qc.ca").as_deref(), Some("gouv.qc.ca"));
+		assert_eq!(registrable("shop.example.com.au").as_deref(), Some("example.com.au"));
+		// Domaines privés: chaque client est son propre domaine
+		assert_eq!(registrable("alice.github.io").as_deref(), Some("alice.github.io"));
+		// Règle '*.ck' et son exception '!www.ck'
+		assert_eq!(registrable("a.b.co.ck").as_deref(), Some("b.co.ck"));
+		assert_eq!(registrable("www.ck").as_deref(), Some("www.ck"));
+		// Un suffixe public seul n'est pas un domaine; une adresse IP en est un
+		assert_eq!(registrable("co.uk"), None);
+		assert_eq!(registrable("com"), None);
+		assert_eq!(registrable("192.168.0.1").as_deref(), Some("192.168.0.1"));
+	}
+
+	#[test]
+	fn degres_de_correspondance() {
+		assert_eq!(compare("login.example.co.uk", "login.example.co.uk"), Some(Match::Exact));
+		assert_eq!(compare("login.example.co.uk", "example.co.uk"), Some(Match::Subdomain));
+		assert_eq!(compare("login.example.co.uk", "mail.example.co.uk"), Some(Match::Domain));
+		assert_eq!(compare("example.co.uk", "other.co.uk"), None);
+		assert_eq!(compare("alice.github.io", "bob.github.io"), None);
+		assert_eq!(compare("example.com", "evilexample.com"), None);
+		assert_eq!(compare("example.com.evil.com", "example.com"), None);
+		assert!(Match::Domain < Match::Subdomain && Match::Subdomain < Match::Exact);
+	}
+}
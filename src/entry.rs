@@ -0,0 +1,294 @@
+// entry.rs
+
+use crate::date;
+use crate::journal;
+use crate::otp;
+
+// Format d'une ligne du fichier mdp:
+//     <mot de passe>∫∆∫<titre>[∫∆∫<clé>=<valeur>]...
+// Les anciennes lignes '<mot de passe>∫∆∫<description>' restent valides:
+// ce sont simplement des entrées sans champ supplémentaire.
+
+pub const SEP: &str = "∫∆∫";
+
+// Champs descriptifs connus (clé dans le fichier)
+pub const USER: &str = "user";
+pub const URL: &str = "url";
+pub const TAGS: &str = "tags";
+pub const FOLDER: &str = "folder";	// Chemin du dossier, ex. 'travail/aws/prod'
+pub const NOTES: &str = "notes";
+pub const OTP: &str = "otp";		// URI otpauth:// ou secret base32 (voir otp.rs)
+pub const TYPE: &str = "type";		// Absent pour un identifiant (voir template.rs)
+
+// Champs descriptifs qu'on peut fixer sur une entrée existante (--set-field)
+pub const EDITABLE: [&str; 5] = [USER, URL, TAGS, FOLDER, NOTES];
+
+// Métadonnées (secondes depuis l'époque UNIX)
+pub const CREATED: &str = "created";
+pub const USED: &str = "used";
+pub const MODIFIED: &str = "modified";
+pub const DELETED: &str = "deleted";		// Présent seulement dans la corbeille
+
+// Échéance de renouvellement: date fixe (secondes UNIX) et/ou âge maximal en jours depuis la modification
+pub const EXPIRES: &str = "expires";
+pub const MAX_AGE: &str = "max_age";
+
+// Pièces jointes: un champ 'attach:<nom>' par fichier, contenu en base64
+pub const ATTACH: &str = "attach:";
+
+// Champs personnalisés: 'field:<nom>' en clair, 'secret:<nom>' masqué à l'affichage et jamais cherché
+pub const CUSTOM: &str = "field:";
+pub const CUSTOM_SECRET: &str = "secret:";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Entry {
+	pub pw: String,
+	pub title: String,
+	pub fields: Vec<(String, String)>,
+}
+
+impl Entry {
+	pub fn new(title: &str, pw: &str) -> Self {
+		Self { pw: pw.to_string(), title: title.to_string(), fields: Vec::new() }
+	}
+
+	/// Décode une ligne du fichier. Une ligne sans séparateur devient une entrée sans mot de passe.
+	pub fn parse(ligne: &str) -> Self {
+		let Some((pw, reste)) = ligne.split_once(SEP) else {
+			return Self::new(ligne, "");
+		};
+		let mut morceaux = reste.split(SEP);
+		let title = morceaux.next().unwrap_or_default();
+		let mut entry = Self::new(title, pw);
+
+		for champ in morceaux {
+			match champ.split_once('=') {
+				Some((cle, valeur)) => entry.fields.push((cle.to_string(), unescape(valeur))),
+				// Champ illisible: on le rattache au titre pour ne rien perdre
+				None => { entry.title.push_str(SEP); entry.title.push_str(champ); }
+			}
+		}
+		entry
+	}
+
+	/// Encode l'entrée en une seule ligne pour le fichier.
+	pub fn to_line(&self) -> String {
+		let mut ligne = format!("{}{SEP}{}", self.pw, self.title);
+		for (cle, valeur) in &self.fields {
+			ligne.push_str(SEP);
+			ligne.push_str(cle);
+			ligne.push('=');
+			ligne.push_str(&escape(valeur));
+		}
+		ligne
+	}
+
+	pub fn get(&self, cle: &str) -> Option<&str> {
+		self.fields.iter()
+			.find(|(c, _)| c == cle)
+			.map(|(_, v)| v.as_str())
+	}
+
+	/// Remplace (ou ajoute) un champ. Une valeur vide retire le champ.
+	pub fn set(&mut self, cle: &str, valeur: &str) {
+		if valeur.is_empty() {
+			self.fields.retain(|(c, _)| c != cle);
+		}
+		else if let Some(champ) = self.fields.iter_mut().find(|(c, _)| c == cle) {
+			champ.1 = valeur.to_string();
+		}
+		else {
+			self.fields.push((cle.to_string(), valeur.to_string()));
+		}
+	}
+
+	/// Lit un champ de date (secondes UNIX).
+	pub fn get_time(&self, cle: &str) -> Option<u64> {
+		self.get(cle).and_then(|v| v.parse().ok())
+	}
+
+	/// Marque l'entrée comme utilisée maintenant (copie, affichage).
+	pub fn touch_used(&mut self) {
+		self.set(USED, &date::now().to_string());
+	}
+
+	/// Marque l'entrée comme créée maintenant.
+	pub fn touch_created(&mut self) {
+		self.set(CREATED, &date::now().to_string());
+	}
+
+	/// Marque l'entrée comme modifiée maintenant (création, mise à jour).
+	pub fn touch_modified(&mut self) {
+		self.set(MODIFIED, &date::now().to_string());
+	}
+
+	/// Même contenu, sans égard à l'usage: dates de création, d'utilisation et de modification,
+	/// compteur d'un code HOTP.
+	pub fn same_content(&self, autre: &Entry) -> bool {
+		let champs = |e: &Entry| e.fields.iter()
+			.filter(|(c, _)| c != CREATED && c != USED && c != MODIFIED)
+			.map(|(c, v)| if c == OTP { (c.clone(), sans_compteur(v)) } else { (c.clone(), v.clone()) })
+			.collect::<Vec<_>>();
+		self.pw == autre.pw && self.title == autre.title && champs(self) == champs(autre)
+	}
+
+	/// Reprend l'usage d'une version plus récente de l'entrée: date d'utilisation et compteur HOTP.
+	/// Un compteur ne recule jamais: un code déjà servi serait refusé.
+	pub fn keep_usage(&mut self, recente: &Entry) {
+		if let Some(utilise) = recente.get(USED) { self.set(USED, utilise); }
+		let (Some(a), Some(b)) = (self.get(OTP), recente.get(OTP)) else { return };
+		if sans_compteur(a) != sans_compteur(b) { return; }
+		if let (Some(avant), Some(apres)) = (compteur(a), compteur(b)) && apres > avant {
+			self.set(OTP, &otp::with_counter(a, apres));
+		}
+	}
+
+	/// Complète l'entrée avec les champs d'une autre (doublon): les champs absents sont repris,
+	/// les étiquettes réunies, la date de création la plus ancienne et les dates d'utilisation
+	/// et de modification les plus récentes conservées.
+	pub fn merge_from(&mut self, autre: &Entry) {
+		for (cle, valeur) in &autre.fields {
+			match cle.as_str() {
+				TAGS => {
+					let mut tags: Vec<String> = self.tags().iter().map(|t| t.to_string()).collect();
+					for t in autre.tags() {
+						if !tags.iter().any(|x| x == t) { tags.push(t.to_string()); }
+					}
+					self.set(TAGS, &tags.join(","));
+				}
+				CREATED => {
+					if self.get_time(CREATED).is_none_or(|t| autre.get_time(CREATED).is_some_and(|a| a < t)) {
+						self.set(CREATED, valeur);
+					}
+				}
+				USED | MODIFIED => {
+					if autre.get_time(cle) > self.get_time(cle) { self.set(cle, valeur); }
+				}
+				_ => {
+					if self.get(cle).is_none() { self.set(cle, valeur); }
+				}
+			}
+		}
+	}
+
+	pub fn tags(&self) -> Vec<&str> {
+		match self.get(TAGS) {
+			Some(t) => t.split(',').map(str::trim).filter(|t| !t.is_empty()).collect(),
+			None => Vec::new(),
+		}
+	}
+
+	pub fn folder(&self) -> Option<&str> {
+		self.get(FOLDER)
+	}
+
+	/// Pièces jointes de l'entrée: (nom, contenu en base64), dans l'ordre du fichier.
+	pub fn attachments(&self) -> Vec<(&str, &str)> {
+		self.fields.iter()
+			.filter_map(|(c, v)| c.strip_prefix(ATTACH).map(|nom| (nom, v.as_str())))
+			.collect()
+	}
+
+	/// Champs personnalisés: (nom, valeur, secret), dans l'ordre du fichier.
+	pub fn custom_fields(&self) -> Vec<(&str, &str, bool)> {
+		self.fields.iter()
+			.filter_map(|(c, v)| match (c.strip_prefix(CUSTOM), c.strip_prefix(CUSTOM_SECRET)) {
+				(Some(nom), _) => Some((nom, v.as_str(), false)),
+				(_, Some(nom)) => Some((nom, v.as_str(), true)),
+				_ => None,
+			})
+			.collect()
+	}
+
+	pub fn custom(&self, nom: &str) -> Option<&str> {
+		self.get(&format!("{CUSTOM}{nom}")).or_else(|| self.get(&format!("{CUSTOM_SECRET}{nom}")))
+	}
+
+	/// Fixe un champ personnalisé, secret ou non; une valeur vide le retire.
+	pub fn set_custom(&mut self, nom: &str, valeur: &str, secret: bool) {
+		let (cle, autre) = if secret { (CUSTOM_SECRET, CUSTOM) } else { (CUSTOM, CUSTOM_SECRET) };
+		self.set(&format!("{autre}{nom}"), "");
+		self.set(&format!("{cle}{nom}"), valeur);
+	}
+
+	/// Date à laquelle l'entrée doit être renouvelée: la plus proche entre la date d'expiration
+	/// et la fin de l'âge maximal, compté depuis la dernière modification.
+	pub fn due(&self) -> Option<u64> {
+		let age = self.get_time(MAX_AGE)
+			.zip(self.get_time(MODIFIED))
+			.map(|(jours, modif)| modif + jours * date::SECS_PER_DAY);
+		match (self.get_time(EXPIRES), age) {
+			(Some(a), Some(b)) => Some(a.min(b)),
+			(a, b) => a.or(b),
+		}
+	}
+}
+
+/// Forme rangée d'un chemin de dossier: segments sans blancs autour, sans '/' superflu.
+/// ' /travail//aws/ ' => 'travail/aws'
+pub fn clean_folder(chemin: &str) -> String {
+	chemin.split('/').map(str::trim).filter(|s| !s.is_empty()).collect::<Vec<_>>().join("/")
+}
+
+/// Vrai si 'cle' est un champ connu de l'entrée (descriptif ou métadonnée).
+pub fn is_builtin(cle: &str) -> bool {
+	[USER, URL, TAGS, FOLDER, NOTES, OTP, TYPE, CREATED, USED, MODIFIED, DELETED, EXPIRES, MAX_AGE].contains(&cle)
+}
+
+/// Vrai si 'nom' peut nommer un champ personnalisé: non vide, sans blanc autour,
+/// ni '=', ni séparateur, ni caractère de contrôle.
+pub fn is_field_name(nom: &str) -> bool {
+	!nom.is_empty() && nom.trim() == nom && !nom.chars().any(|c| c == '=' || c == '∫' || c.is_control())
+}
+
+/// Nom de pièce jointe utilisable comme clé: ni '=', ni séparateur, ni caractère de contrôle.
+pub fn clean_attachment_name(nom: &str) -> String {
+	nom.trim().chars().map(|c| if c == '=' || c == '∫' || c.is_control() { '_' } else { c }).collect()
+}
+
+// Compteur d'une URI hotp.
+fn compteur(source: &str) -> Option<u64> {
+	match otp::Otp::parse(source)?.kind {
+		otp::Kind::Hotp { counter } => Some(counter),
+		otp::Kind::Totp { .. } => None,
+	}
+}
+
+// Source 2FA sans son compteur, pour comparer deux versions d'une même entrée.
+fn sans_compteur(source: &str) -> String {
+	if compteur(source).is_some() { otp::with_counter(source, 0) } else { source.to_string() }
+}
+
+/// Lignes du fichier à partir des entrées, dans l'ordre.
+pub fn to_lines(entries: &[Entry]) -> Vec<String> {
+	entries.iter().map(Entry::to_line).collect()
+}
+
+/// Vrai si le texte peut servir de titre ou de mot de passe, qui sont écrits tels quels:
+/// ni séparateur, ni marque du journal, ni saut de ligne.
+pub fn is_storable(texte: &str) -> bool {
+	!texte.contains(SEP) && !texte.contains(journal::MARK) && !texte.contains(['\n', '\r'])
+}
+
+// Les valeurs ne peuvent contenir de saut de ligne (le fichier est lu ligne par ligne)
+// ni de '∫', qui commence le séparateur et la marque du journal.
+fn escape(valeur: &str) -> String {
+	valeur.replace('\\', "\\\\").replace('\n', "\\n").replace('∫', "\\i")
+}
+
+fn unescape(valeur: &str) -> String {
+	let mut sortie = String::with_capacity(valeur.len());
+	let mut chars = valeur.chars();
+	while let Some(c) = chars.next() {
+		if c == '\\' {
+			match chars.next() {
+				Some('n') => sortie.push('\n'),
+				Some('i') => sortie.push('∫'),
+				Some(autre) => sortie.push(autre),
+				None => sortie.push('\\'),
+			}
+		}
+		else { sortie.push(c); }
+	}
+	sortie
+}
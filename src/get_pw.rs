@@ -19,6 +19,10 @@ pub struct termios {
 }
 #[cfg(target_os = "linux")] const ECHO: u32 = 0o000010;
 #[cfg(target_os = "linux")] const ICANON: u32 = 0o000002;
+#[cfg(target_os = "linux")] const ISIG: u32 = 0o000001;
+#[cfg(target_os = "linux")] const VTIME: usize = 5;
+#[cfg(target_os = "linux")] const VMIN: usize = 6;
+#[cfg(target_os = "linux")] const TIOCGWINSZ: u64 = 0x5413;
 
 #[cfg(target_os = "macos")]
 #[repr(C)]
@@ -35,17 +39,32 @@ pub struct termios {
 }
 #[cfg(target_os = "macos")] const ECHO: usize = 0x00000008;
 #[cfg(target_os = "macos")] const ICANON: usize = 0x00000100;
+#[cfg(target_os = "macos")] const ISIG: usize = 0x00000080;
+#[cfg(target_os = "macos")] const VMIN: usize = 16;
+#[cfg(target_os = "macos")] const VTIME: usize = 17;
+#[cfg(target_os = "macos")] const TIOCGWINSZ: u64 = 0x40087468;
 
 const TCSADRAIN: i32 = 1;
 
+#[repr(C)]
+#[derive(Default)]
+struct Winsize {
+	ws_row: u16,
+	ws_col: u16,
+	ws_xpixel: u16,
+	ws_ypixel: u16,
+}
+
 unsafe extern "C" {
 	fn tcgetattr(fd: i32, termios_p: *mut termios) -> i32;
 	fn tcsetattr(fd: i32, optional_actions: i32, termios_p: *const termios) -> i32;
+	fn isatty(fd: i32) -> i32;
+	fn ioctl(fd: i32, request: u64, ...) -> i32;
 }
 
 // --- Gestion du terminal (RAII) ---
 
-struct RawModeGuard {
+pub struct RawModeGuard {
 	fd: i32,
 	original: termios,
 }
@@ -56,6 +75,43 @@ impl Drop for RawModeGuard {
 	}
 }
 
+/// Vrai si le descripteur est relié à un terminal.
+pub fn is_tty(fd: i32) -> bool {
+	unsafe { isatty(fd) == 1 }
+}
+
+/// Nombre de lignes du terminal (24 si inconnu).
+pub fn terminal_rows() -> usize {
+	let mut ws = Winsize::default();
+	let fd = io::stdout().as_raw_fd();
+	if unsafe { ioctl(fd, TIOCGWINSZ, &mut ws as *mut Winsize) } == 0 && ws.ws_row > 0 {
+		ws.ws_row as usize
+	}
+	else { 24 }
+}
+
+/// Passe le terminal en mode brut (sans écho, caractère par caractère).
+/// 'keys': les touches de contrôle (Ctrl-C...) sont lues comme des caractères, et une lecture
+/// revient après 1/10 s sans donnée (utile pour distinguer Esc seul d'une flèche).
+/// Le mode d'origine est rétabli quand le gardien est libéré.
+pub fn raw_mode(fd: i32, keys: bool) -> Option<RawModeGuard> {
+	let mut original = unsafe { std::mem::zeroed() };
+	if unsafe { tcgetattr(fd, &mut original) } != 0 {
+		return None;
+	}
+	let guard = RawModeGuard { fd, original };
+
+	let mut hidden = original;
+	hidden.c_lflag &= !(ECHO | ICANON);
+	if keys {
+		hidden.c_lflag &= !ISIG;
+		hidden.c_cc[VMIN] = 0;
+		hidden.c_cc[VTIME] = 1;
+	}
+	unsafe { tcsetattr(fd, TCSADRAIN, &hidden) };
+	Some(guard)
+}
+
 // --- La Macro ---
 
 #[macro_export]
@@ -71,52 +127,75 @@ macro_rules! get_pw {
 
 // --- L'implémentation ---
 
-pub fn get_pw_impl(message: &str, mask: char) -> String {
+/// Retourne le mot de passe et vrai si des caractères non ASCII ont été ignorés,
+/// pour que l'appelant avertisse l'usager dans sa langue.
+pub fn get_pw_impl(message: &str, mask: char) -> (String, bool) {
+	lire_masque(message, mask, false)
+}
+
+/// Comme get_pw!, mais pour un secret qui n'est pas un mot de passe (note, numéro de carte,
+/// champ secret): les caractères non ASCII (accents...) sont acceptés.
+/// Les mots de passe restent en ASCII: un caractère qui était ignoré à la saisie
+/// changerait sinon le mot de passe du fichier mdp.
+pub fn get_secret(message: &str) -> String {
+	lire_masque(message, '*', true).0
+}
+
+fn lire_masque(message: &str, mask: char, utf8: bool) -> (String, bool) {
 	let stdin = io::stdin();
 	let fd = stdin.as_raw_fd();
 	let mut password = String::new();
 
-	print!("{}", message);
-	let _ = io::stdout().flush();
-
-	let mut original = unsafe { std::mem::zeroed() };
-	if unsafe { tcgetattr(fd, &mut original) } != 0 {
-		return String::new();
-	}
+	// L'invite va vers stderr: stdout reste propre pour '$(mdp -g ...)'
+	eprint!("{}", message);
+	let _ = io::stderr().flush();
 
-	let _guard = RawModeGuard { fd, original };
-
-	let mut hidden = original;
-	hidden.c_lflag &= !(ECHO | ICANON);
-	unsafe { tcsetattr(fd, TCSADRAIN, &hidden) };
+	let Some(_guard) = raw_mode(fd, false) else {
+		return (String::new(), false);
+	};
 
 	let mut buffer = [0u8; 1];
 	let mut handle = stdin.lock();
+	let mut sequence: Vec<u8> = Vec::new();	// Caractère UTF-8 en cours de lecture
+	let mut ignores = false;		// Caractères non ASCII écartés d'un mot de passe
 
 	loop {
 		if handle.read_exact(&mut buffer).is_ok() {
 			match buffer[0] {
 				b'\n' | 0x0D => break,
-				0x7F | 0x08 => {
-					if !password.is_empty() {
-						password.pop();
-						print!("\x08 \x08");
-						let _ = io::stdout().flush();
-					}
+				0x7F | 0x08 if !password.is_empty() => {
+					password.pop();
+					eprint!("\x08 \x08");
+					let _ = io::stderr().flush();
 				}
 				0x1B => {
 					let mut seq = [0u8; 2];
 					let _ = handle.read(&mut seq);
 				}
-				c if c >= 32 && c <= 126 => {
+				c if (32..=126).contains(&c) => {
 					password.push(c as char);
-					print!("{}", mask);
-					let _ = io::stdout().flush();
+					eprint!("{}", mask);
+					let _ = io::stderr().flush();
+				}
+				c if utf8 && c >= 0x80 => {
+					sequence.push(c);
+					match std::str::from_utf8(&sequence) {
+						Ok(car) => {
+							password.push_str(car);
+							sequence.clear();
+							eprint!("{}", mask);
+							let _ = io::stderr().flush();
+						}
+						// Séquence invalide: on l'oublie; incomplète: on attend la suite
+						Err(e) if e.error_len().is_some() => sequence.clear(),
+						Err(_) => { }
+					}
 				}
+				c if c >= 0x80 => ignores = true,
 				_ => {}
 			}
 		}
 	}
-	println!();
-	password
+	eprintln!();
+	(password, ignores)
 }
@@ -0,0 +1,147 @@
+// journal.rs
+
+// Journal des opérations, conservé (chiffré) dans le fichier mdp à la suite des entrées.
+// Une ligne par entrée touchée:
+//     ∫≡∫<no>∫≡∫<date>∫≡∫<opération>∫≡∫<-|+>∫≡∫<ligne de l'entrée>
+// '-' : l'entrée telle qu'elle était avant (retirée), '+' : telle qu'elle est après (ajoutée).
+// Une modification donne donc une paire -/+ sous le même numéro d'opération.
+// L'usage (date d'utilisation, compteur HOTP) et la purge de la corbeille n'y sont pas inscrits.
+// Une pièce jointe encore présente après l'opération n'y figure que par son empreinte
+// ('#<sha256>'); son contenu complet n'est gardé que si l'opération le fait disparaître.
+
+use crate::entry::{self, Entry};
+use crate::otp;
+
+pub const MARK: &str = "∫≡∫";
+
+// Nombre d'opérations conservées et taille maximale du journal; les plus anciennes sont oubliées.
+const JOURNAL_MAX: usize = 100;
+const JOURNAL_MAX_BYTES: usize = 4 * 1024 * 1024;
+
+// Début d'une empreinte: le base64 d'une pièce jointe ne contient jamais '#'
+const EMPREINTE: char = '#';
+
+#[derive(Debug, Clone)]
+pub struct Operation {
+	pub seq: u64,
+	pub time: u64,
+	pub op: String,
+	pub removed: Vec<Entry>,
+	pub added: Vec<Entry>,
+}
+
+pub fn is_journal_line(ligne: &str) -> bool {
+	ligne.starts_with(MARK)
+}
+
+/// Regroupe les lignes du journal par opération, dans l'ordre du fichier.
+pub fn parse_lines<'a>(lignes: impl Iterator<Item = &'a String>) -> Vec<Operation> {
+	let mut ops: Vec<Operation> = Vec::new();
+	for ligne in lignes {
+		let morceaux: Vec<&str> = ligne[MARK.len()..].splitn(5, MARK).collect();
+		let [seq, time, op, sens, contenu] = morceaux[..] else { continue };
+		let (Ok(seq), Ok(time)) = (seq.parse::<u64>(), time.parse::<u64>()) else { continue };
+
+		if ops.last().is_none_or(|o| o.seq != seq) {
+			ops.push(Operation { seq, time, op: op.to_string(), removed: Vec::new(), added: Vec::new() });
+		}
+		if let Some(courante) = ops.last_mut() {
+			let entry = Entry::parse(contenu);
+			if sens == "-" { courante.removed.push(entry); } else { courante.added.push(entry); }
+		}
+	}
+	ops
+}
+
+pub fn to_lines(ops: &[Operation]) -> Vec<String> {
+	let mut lignes = Vec::new();
+	for o in ops {
+		let entete = format!("{MARK}{}{MARK}{}{MARK}{}{MARK}", o.seq, o.time, o.op);
+		lignes.extend(o.removed.iter().map(|e| format!("{entete}-{MARK}{}", e.to_line())));
+		lignes.extend(o.added.iter().map(|e| format!("{entete}+{MARK}{}", e.to_line())));
+	}
+	lignes
+}
+
+/// Ajoute une opération à partir des lignes d'entrées avant et après la commande.
+/// Seules les différences sont conservées; rien n'est ajouté si rien n'a changé.
+pub fn record(journal: &mut Vec<Operation>, op: &str, time: u64, avant: &[String], apres: &[String]) {
+	// Différence de multiensembles: une ligne identique des deux côtés n'a pas bougé
+	let mut restantes: Vec<&String> = apres.iter().collect();
+	let mut removed = Vec::new();
+	for ligne in avant {
+		match restantes.iter().position(|l| *l == ligne) {
+			Some(i) => { restantes.swap_remove(i); }
+			None => removed.push(Entry::parse(ligne)),
+		}
+	}
+	let added: Vec<Entry> = restantes.into_iter().map(|l| condense(&Entry::parse(l))).collect();
+	if removed.is_empty() && added.is_empty() { return; }
+
+	// Une pièce jointe retirée qui se retrouve après l'opération n'a pas besoin de son contenu
+	let apres: Vec<&str> = added.iter().flat_map(Entry::attachments).map(|(_, v)| v).collect();
+	for e in removed.iter_mut() {
+		for (_, valeur) in e.fields.iter_mut().filter(|(c, _)| c.starts_with(entry::ATTACH)) {
+			let courte = empreinte(valeur);
+			if apres.contains(&courte.as_str()) { *valeur = courte; }
+		}
+	}
+
+	let seq = journal.last().map_or(1, |o| o.seq + 1);
+	journal.push(Operation { seq, time, op: op.to_string(), removed, added });
+	if journal.len() > JOURNAL_MAX {
+		journal.drain(..journal.len() - JOURNAL_MAX);
+	}
+	// La dernière opération est toujours gardée, même seule au-delà de la taille maximale
+	let taille = |o: &Operation| to_lines(std::slice::from_ref(o)).iter().map(|l| l.len() + 1).sum::<usize>();
+	let mut total: usize = journal.iter().map(taille).sum();
+	while total > JOURNAL_MAX_BYTES && journal.len() > 1 {
+		total -= taille(&journal.remove(0));
+	}
+}
+
+// Empreinte d'un contenu de pièce jointe; une empreinte reste telle quelle.
+fn empreinte(contenu: &str) -> String {
+	if contenu.starts_with(EMPREINTE) { return contenu.to_string(); }
+	let hex: String = otp::sha256(contenu.as_bytes()).iter().map(|o| format!("{o:02x}")).collect();
+	format!("{EMPREINTE}{hex}")
+}
+
+/// Copie de l'entrée où chaque pièce jointe est réduite à son empreinte, pour la comparer
+/// à une copie du journal.
+pub fn condense(e: &Entry) -> Entry {
+	let mut copie = e.clone();
+	for (_, valeur) in copie.fields.iter_mut().filter(|(c, _)| c.starts_with(entry::ATTACH)) {
+		*valeur = empreinte(valeur);
+	}
+	copie
+}
+
+/// Remet le contenu des pièces jointes réduites à leur empreinte, pris dans les 'sources'
+/// (les versions que l'annulation remplace). Faux si un contenu est introuvable.
+pub fn expand(e: &mut Entry, sources: &[Entry]) -> bool {
+	for (_, valeur) in e.fields.iter_mut().filter(|(c, v)| c.starts_with(entry::ATTACH) && v.starts_with(EMPREINTE)) {
+		let contenu = sources.iter()
+			.flat_map(Entry::attachments)
+			.find(|(_, v)| empreinte(v) == *valeur);
+		match contenu {
+			Some((_, v)) => *valeur = v.to_string(),
+			None => return false,
+		}
+	}
+	true
+}
+
+/// Oublie les entrées détruites définitivement: leur mot de passe ne doit pas survivre dans le journal.
+/// Toutes les versions d'une entrée sont oubliées, y compris celles d'un ancien mot de passe:
+/// une entrée se reconnaît à son titre et à sa date de création, qui ne changent pas.
+/// Les opérations devenues vides sont retirées.
+pub fn forget(journal: &mut Vec<Operation>, detruites: &[Entry]) {
+	let oubliee = |e: &Entry| detruites.iter()
+		.any(|d| d.title == e.title && d.get(entry::CREATED) == e.get(entry::CREATED));
+	for o in journal.iter_mut() {
+		o.removed.retain(|e| !oubliee(e));
+		o.added.retain(|e| !oubliee(e));
+	}
+	journal.retain(|o| !o.removed.is_empty() || !o.added.is_empty());
+}
@@ -3,7 +3,7 @@
 use std::env;
 
 const OPTIONS_FR: &str =
-r#"[-f <motif>|-d <motif>|-n <desc>|-a <desc> <password>] [-l <longueur>] [fichier mdp]
+r#"[-f <motif>|-d <motif>|-g <nom>|-n <desc>|-a [<desc> [--password-stdin]]] [-l <longueur>] [-s] [-y] [--accents] [--regex] [--index N|--first|--exact <titre>] [fichier mdp]
 
 Gestion des mots de passe dans un fichier crypté.
 
@@ -16,18 +16,82 @@ en ligne de commande  fichier_mots_de_passe: Lu/sauvegardé dans le dossier de l
 
 Commandes: -- mutuellement exclusives
     -f/--find <motif>                      Cherche une entrée à partir d'un motif.
-    -d/--del <motif>                       Supprime une entrée à partir d'un motif.
+                                           Le motif vise la description, jamais le mot de passe.
+                                           Sélecteurs: title:, user:, url:, tag:, notes:, type:, folder: (ex. 'user:bob')
+    -d/--del <motif>                       Supprime une ou plusieurs entrées (ex. 1,3,5-7 ou tout) à partir d'un motif.
+    -g/--get <nom>                         Affiche seulement le mot de passe d'une entrée (pour les scripts).
+                                           --field <password|user|url|notes|otp|nom d'un champ> pour un autre champ.
+    --trash                                Liste la corbeille (entrées supprimées par -d).
+    --undelete <motif>                     Restaure une ou plusieurs entrées de la corbeille.
+    --empty-trash [--older-than <jours>]   Détruit définitivement le contenu de la corbeille.
+    --undo [N]                             Annule les N dernières opérations (défaut 1); --empty-trash est définitif.
+    --log                                  Affiche le journal des opérations (sans les mots de passe).
+    --dedupe                               Fusionne les entrées de même titre (sans égard à la casse et aux accents).
+    --otp <motif>                          Affiche (et copie) le code 2FA courant d'une entrée (TOTP ou HOTP).
+    --otp-set <motif>                      Associe à une entrée un secret 2FA: URI otpauth:// ou secret base32.
+    --import-otp                           Importe des comptes 2FA lus sur l'entrée standard: URI otpauth://
+                                           ou exports de Google Authenticator (otpauth-migration://).
+    --qr <motif>                           Affiche le secret d'une entrée en code QR dans le terminal (réseau Wi-Fi
+                                           pour le type wifi). --field <nom> pour un autre champ (ex. otp).
+    --tags                                 Liste les étiquettes et leur nombre d'entrées.
+    --folders                              Affiche l'arbre des dossiers et leur nombre d'entrées.
+    --list [motif]                         Liste les titres des entrées (toutes, ou celles du motif), sans aucun secret.
+                                           Colonnes (--columns a,b): user, url, tags, folder, type, created, modified, used, due.
+                                           Tri (--sort): title, folder, type, created, modified, used, due; --reverse; --tree (arbre des dossiers).
+                                           --older-than <jours>: seulement les entrées non modifiées depuis <jours> jours.
+    --due [jours]                          Liste les entrées dont l'échéance tombe d'ici [jours] jours (30 par défaut)
+                                           ou est dépassée. Un rappel est affiché à chaque ouverture s'il y a du retard.
+    --set-expiry <motif>                   Fixe l'échéance d'une entrée (--expires, --max-age); sans option, la retire.
+    --attach <motif> <fichier>             Joint un fichier à une entrée, chiffré dans le fichier mdp (512 Kio au plus,
+                                           2 Mio par entrée).
+    --attachments [motif]                  Liste les pièces jointes des entrées et leur taille.
+    --extract <motif> <nom>                Écrit une pièce jointe sur la sortie standard, ou dans un fichier avec --to <chemin>
+                                           (permissions 0600).
+    --detach <motif> <nom>                 Retire une pièce jointe d'une entrée.
+    --url <url>                            Cherche les entrées du même domaine que l'URL (ex. 'https://login.exemple.co.uk/x'
+                                           trouve 'exemple.co.uk'), la plus proche d'abord, puis copie comme -f.
+                                           Avec -a/-n: URL de l'entrée créée.
+    --set-field <motif> <nom>[=<valeur>]   Ajoute un champ à une entrée (ex. 'compte=12345'); --secret le masque à
+                                           l'affichage. Sans valeur, elle est demandée (masquée avec --secret); vide, le champ est retiré.
+                                           Fixe aussi les champs user, url, tags, folder et notes de l'entrée,
+                                           et ceux de son type (ex. 'number', 'expiry' d'une carte créée par -a --type card).
     -n/--new <description>                 Créé une nouvelle entrée à partir d'une description.
-    -a/--add <description> <mot de passe>  Créé une nouvelle entrée manuellement.
+    -a/--add <description>                 Créé une nouvelle entrée manuellement; le mot de passe est demandé (masqué, deux fois).
+                                           Sans description, un assistant demande chaque champ (titre, utilisateur, URL, notes...).
+                                           --password-stdin le lit plutôt sur l'entrée standard (ex. 'pass show x | mdp -a x --password-stdin').
+                                           L'ancienne forme '-a <description> <mot de passe>' reste acceptée, avec un avertissement:
+                                           l'argument qui suit la description est le mot de passe, sauf une option de -a
+                                           (ex. 'mdp -a gmail --type login autre.bin' pour un autre fichier). En dernier,
+                                           un fichier existant ou un nom en '.bin' est refusé comme mot de passe.
 
     -ver/--version  Affiche un version courte/longue selon.
     -h/--help       Montre l'usage/ce message d'aide et termine.
 
 Option:
-	-l/--long <longueur>    Nombre de caracrère du mot de passe à générer. Défaut 12."#;
+	-l/--long <longueur>    Nombre de caracrère du mot de passe à générer. Défaut 12.
+	-s/--show               Affiche les mots de passe en clair dans les listes (masqués par défaut).
+	-y/--yes                Répond oui aux confirmations (scripts). Avec -a/-n, refuse de remplacer une entrée existante.
+	--update                Avec -a/-n: met à jour l'entrée de même titre sans demander.
+	--type <type>           Type de l'entrée créée par -a: login, note, card, wifi, licence, ssh, recovery.
+	                        L'assistant (-a --type card) demande les champs du type (numéro, expiration...).
+	--tag <étiquette>       Avec -f/-d/-g...: seulement les entrées qui portent l'étiquette (répétable).
+	                        Avec -a/-n: ajoute l'étiquette à l'entrée créée.
+	--folder <chemin>       Avec -f/-d/-g...: seulement les entrées du dossier, sous-dossiers compris.
+	                        Avec -a/-n: range l'entrée créée dans le dossier (ex. 'travail/aws/prod').
+	--expires <AAAA-MM-JJ>  Avec -a/-n/--set-expiry: date d'expiration de l'entrée.
+	--max-age <jours>       Avec -a/-n/--set-expiry: âge maximal du mot de passe, compté depuis sa modification.
+	--match <degré>         Avec --url: domain (même domaine, défaut), subdomain (même hôte ou hôte parent), exact.
+	--accents               Recherche sensible aux accents ('societe' ne trouve plus 'Société').
+	--regex                 Le motif de -f/-d est une expression régulière (ex. '^VPN-|\.gouv\.qc\.ca$').
+
+Sélection sans interaction (scripts): -- échoue si vide (code 2) ou ambiguë (code 3)
+	--index <N>             Choisit la N-ième entrée de la liste. Le classement ignore alors l'usage récent:
+	                        un même N désigne la même entrée d'une fois à l'autre.
+	--first                 Choisit la première (la plus pertinente) entrée.
+	--exact <titre>         Choisit l'entrée dont le titre est exactement <titre>."#;
 
 const OPTIONS_ES: &str =
-r#"[-f <patrón>|-d <patrón>|-n <desc>|-a <desc> <contraseña>] [-l <longitud>] [archivo contras]
+r#"[-f <patrón>|-d <patrón>|-g <nombre>|-n <desc>|-a [<desc> [--password-stdin]]] [-l <longitud>] [-s] [-y] [--accents] [--regex] [--index N|--first|--exact <título>] [archivo contras]
 
 Gestión de contraseñas en un archivo encriptado.
 
@@ -40,18 +104,82 @@ en línea de comandos  archivo_contraseñas: Leído/guardado en la carpeta del e
 
 Comandos: -- mutuamente excluyentes
     -f/--find <patrón>                     Busca una entrada usando un patrón.
-    -d/--del <patrón>                      Elimina una entrada usando un patrón.
+                                           El patrón busca en la descripción, nunca en la contraseña.
+                                           Selectores: title:, user:, url:, tag:, notes:, type:, folder: (ej. 'user:bob')
+    -d/--del <patrón>                      Elimina una o varias entradas (ej. 1,3,5-7 o todo) usando un patrón.
+    -g/--get <nombre>                      Muestra solo la contraseña de una entrada (para scripts).
+                                           --field <password|user|url|notes|otp|nombre de un campo> para otro campo.
+    --trash                                Lista la papelera (entradas eliminadas con -d).
+    --undelete <patrón>                    Restaura una o varias entradas de la papelera.
+    --empty-trash [--older-than <días>]    Destruye definitivamente el contenido de la papelera.
+    --undo [N]                             Anula las N últimas operaciones (predeterminado 1); --empty-trash es definitivo.
+    --log                                  Muestra el registro de operaciones (sin las contraseñas).
+    --dedupe                               Fusiona las entradas con el mismo título (sin distinguir mayúsculas ni acentos).
+    --otp <patrón>                         Muestra (y copia) el código 2FA actual de una entrada (TOTP o HOTP).
+    --otp-set <patrón>                     Asocia a una entrada un secreto 2FA: URI otpauth:// o secreto base32.
+    --import-otp                           Importa cuentas 2FA leídas de la entrada estándar: URI otpauth://
+                                           o exportaciones de Google Authenticator (otpauth-migration://).
+    --qr <patrón>                          Muestra el secreto de una entrada como código QR en la terminal (red Wi-Fi
+                                           para el tipo wifi). --field <nombre> para otro campo (ej. otp).
+    --tags                                 Lista las etiquetas y su número de entradas.
+    --folders                              Muestra el árbol de carpetas y su número de entradas.
+    --list [patrón]                        Lista los títulos de las entradas (todas, o las del patrón), sin ningún secreto.
+                                           Columnas (--columns a,b): user, url, tags, folder, type, created, modified, used, due.
+                                           Orden (--sort): title, folder, type, created, modified, used, due; --reverse; --tree (árbol de carpetas).
+                                           --older-than <días>: solo las entradas sin modificar desde hace <días> días.
+    --due [días]                           Lista las entradas cuyo vencimiento llega en los próximos [días] días (30 por
+                                           defecto) o ya pasó. Un aviso se muestra en cada apertura si hay retraso.
+    --set-expiry <patrón>                  Fija el vencimiento de una entrada (--expires, --max-age); sin opción, lo quita.
+    --attach <patrón> <archivo>            Adjunta un archivo a una entrada, cifrado en el archivo de contraseñas (512 KiB
+                                           como máximo, 2 MiB por entrada).
+    --attachments [patrón]                 Lista los adjuntos de las entradas y su tamaño.
+    --extract <patrón> <nombre>            Escribe un adjunto en la salida estándar, o en un archivo con --to <ruta>
+                                           (permisos 0600).
+    --detach <patrón> <nombre>             Quita un adjunto de una entrada.
+    --url <url>                            Busca las entradas del mismo dominio que la URL (ej. 'https://login.ejemplo.co.uk/x'
+                                           encuentra 'ejemplo.co.uk'), la más cercana primero, y copia como -f.
+                                           Con -a/-n: URL de la entrada creada.
+    --set-field <patrón> <nombre>[=<valor>] Añade un campo a una entrada (ej. 'cuenta=12345'); --secret lo oculta
+                                           al mostrarlo. Sin valor, se pide (oculto con --secret); vacío, el campo se quita.
+                                           También fija los campos user, url, tags, folder y notes de la entrada,
+                                           y los de su tipo (ej. 'number', 'expiry' de una tarjeta creada con -a --type card).
     -n/--new <descripción>                 Crea una nueva entrada a partir de una descripción.
-    -a/--add <descripción> <contraseña>    Crea una nueva entrada manualmente.
+    -a/--add <descripción>                 Crea una nueva entrada manualmente; la contraseña se pide (oculta, dos veces).
+                                           Sin descripción, un asistente pide cada campo (título, usuario, URL, notas...).
+                                           --password-stdin la lee de la entrada estándar (ej. 'pass show x | mdp -a x --password-stdin').
+                                           La forma antigua '-a <descripción> <contraseña>' sigue aceptada, con una advertencia:
+                                           el argumento que sigue a la descripción es la contraseña, salvo una opción de -a
+                                           (ej. 'mdp -a gmail --type login otro.bin' para otro archivo). Al final,
+                                           un archivo existente o un nombre en '.bin' se rechaza como contraseña.
 
     -ver/--version  Muestra una versión corta/larga.
     -h/--help       Muestra el uso/este mensaje de ayuda y termina.
 
 Opción:
-    -l/--long <longitud>    Número de caracteres de la contraseña a generar. Predeterminado 12."#;
+    -l/--long <longitud>    Número de caracteres de la contraseña a generar. Predeterminado 12.
+    -s/--show               Muestra las contraseñas en claro en las listas (ocultas por defecto).
+    -y/--yes                Responde sí a las confirmaciones (scripts). Con -a/-n, no reemplaza una entrada existente.
+    --update                Con -a/-n: actualiza la entrada del mismo título sin preguntar.
+    --type <tipo>           Tipo de la entrada creada por -a: login, note, card, wifi, licence, ssh, recovery.
+                            El asistente (-a --type card) pide los campos del tipo (número, vencimiento...).
+    --tag <etiqueta>        Con -f/-d/-g...: solo las entradas con la etiqueta (repetible).
+                            Con -a/-n: añade la etiqueta a la entrada creada.
+    --folder <ruta>         Con -f/-d/-g...: solo las entradas de la carpeta, subcarpetas incluidas.
+                            Con -a/-n: guarda la entrada creada en la carpeta (ej. 'trabajo/aws/prod').
+    --expires <AAAA-MM-DD>  Con -a/-n/--set-expiry: fecha de vencimiento de la entrada.
+    --max-age <días>        Con -a/-n/--set-expiry: edad máxima de la contraseña, contada desde su modificación.
+    --match <grado>         Con --url: domain (mismo dominio, por defecto), subdomain (mismo host o host padre), exact.
+    --accents               Búsqueda sensible a los acentos ('ano' ya no encuentra 'Año').
+    --regex                 El patrón de -f/-d es una expresión regular (ej. '^VPN-|\.gouv\.qc\.ca$').
+
+Selección sin interacción (scripts): -- falla si vacía (código 2) o ambigua (código 3)
+    --index <N>             Elige la N-ésima entrada de la lista. La clasificación ignora entonces el uso reciente:
+                            un mismo N designa la misma entrada de una vez a otra.
+    --first                 Elige la primera entrada (la más pertinente).
+    --exact <título>        Elige la entrada cuyo título es exactamente <título>."#;
 
 const OPTIONS_EN: &str =
-r#"[-f <pattern>|-d <pattern>|-n <desc>|-a <desc> <password>] [-l <length>] [pwd file]
+r#"[-f <pattern>|-d <pattern>|-g <name>|-n <desc>|-a [<desc> [--password-stdin]]] [-l <length>] [-s] [-y] [--accents] [--regex] [--index N|--first|--exact <title>] [pwd file]
 
 Password management in an encrypted file.
 
@@ -64,21 +192,117 @@ In command line     password_file: Read/saved in the executable's directory.
 
 Commands: -- mutually exclusive
     -f/--find <pattern>                    Search for an entry based on a pattern.
-    -d/--del <pattern>                     Delete an entry based on a pattern.
+                                           The pattern targets the description, never the password.
+                                           Selectors: title:, user:, url:, tag:, notes:, type:, folder: (e.g. 'user:bob')
+    -d/--del <pattern>                     Delete one or more entries (e.g. 1,3,5-7 or all) based on a pattern.
+    -g/--get <name>                        Print only the password of an entry (for scripts).
+                                           --field <password|user|url|notes|otp|field name> for another field.
+    --trash                                List the trash (entries deleted with -d).
+    --undelete <pattern>                   Restore one or more entries from the trash.
+    --empty-trash [--older-than <days>]    Permanently destroy the contents of the trash.
+    --undo [N]                             Revert the last N operations (default 1); --empty-trash is final.
+    --log                                  Show the operation journal (without passwords).
+    --dedupe                               Merge entries with the same title (ignoring case and accents).
+    --otp <pattern>                        Show (and copy) the current 2FA code of an entry (TOTP or HOTP).
+    --otp-set <pattern>                    Attach a 2FA secret to an entry: otpauth:// URI or base32 secret.
+    --import-otp                           Import 2FA accounts read from standard input: otpauth:// URIs
+                                           or Google Authenticator exports (otpauth-migration://).
+    --qr <pattern>                         Show an entry's secret as a QR code in the terminal (Wi-Fi network
+                                           for the wifi type). --field <name> for another field (e.g. otp).
+    --tags                                 List tags and their number of entries.
+    --folders                              Show the folder tree and their number of entries.
+    --list [pattern]                       List entry titles (all, or those matching the pattern), never any secret.
+                                           Columns (--columns a,b): user, url, tags, folder, type, created, modified, used, due.
+                                           Sort (--sort): title, folder, type, created, modified, used, due; --reverse; --tree (folder tree).
+                                           --older-than <days>: only entries not modified for <days> days.
+    --due [days]                           List entries due for rotation within [days] days (30 by default) or overdue.
+                                           A reminder is shown on every unlock while something is overdue.
+    --set-expiry <pattern>                 Set an entry's expiry (--expires, --max-age); without either, remove it.
+    --attach <pattern> <file>              Attach a file to an entry, encrypted inside the password file (512 KiB at most,
+                                           2 MiB per entry).
+    --attachments [pattern]                List the entries' attachments and their size.
+    --extract <pattern> <name>             Write an attachment to standard output, or to a file with --to <path>
+                                           (mode 0600).
+    --detach <pattern> <name>              Remove an attachment from an entry.
+    --url <url>                            Look up entries for the URL's domain (e.g. 'https://login.example.co.uk/x'
+                                           finds 'example.co.uk'), closest first, then copy as with -f.
+                                           With -a/-n: URL of the new entry.
+    --set-field <pattern> <name>[=<value>] Add a field to an entry (e.g. 'account=12345'); --secret masks it in
+                                           listings. Without a value, it is prompted for (hidden with --secret); empty removes the field.
+                                           Also sets the entry's user, url, tags, folder and notes fields,
+                                           and its type's fields (e.g. 'number', 'expiry' of a card made with -a --type card).
     -n/--new <description>                 Create a new entry based on a description.
-    -a/--add <description> <password>      Create a new entry manually.
+    -a/--add <description>                 Create a new entry manually; the password is prompted for (hidden, twice).
+                                           Without a description, a wizard asks for each field (title, username, URL, notes...).
+                                           --password-stdin reads it from standard input instead (e.g. 'pass show x | mdp -a x --password-stdin').
+                                           The old form '-a <description> <password>' is still accepted, with a warning:
+                                           the argument after the description is the password, unless it is an -a option
+                                           (e.g. 'mdp -a gmail --type login other.bin' for another file). In last
+                                           position, an existing file or a '.bin' name is refused as a password.
 
     -ver/--version  Display a short/long version accordingly.
     -h/--help       Show usage/this help message and exit.
 
 Option:
-    -l/--long <length>      Number of characters for the password to generate. Default 12."#;
+    -l/--long <length>      Number of characters for the password to generate. Default 12.
+    -s/--show               Show passwords in clear in listings (masked by default).
+    -y/--yes                Answer yes to confirmations (scripts). With -a/-n, refuses to replace an existing entry.
+    --update                With -a/-n: update the entry with the same title without asking.
+    --type <type>           Type of the entry created by -a: login, note, card, wifi, licence, ssh, recovery.
+                            The wizard (-a --type card) asks for the type's fields (number, expiry...).
+    --tag <tag>             With -f/-d/-g...: only entries carrying the tag (repeatable).
+                            With -a/-n: add the tag to the created entry.
+    --folder <path>         With -f/-d/-g...: only entries in the folder, subfolders included.
+                            With -a/-n: file the created entry in the folder (e.g. 'work/aws/prod').
+    --expires <YYYY-MM-DD>  With -a/-n/--set-expiry: expiry date of the entry.
+    --max-age <days>        With -a/-n/--set-expiry: maximum password age, counted from its last change.
+    --match <level>         With --url: domain (same domain, default), subdomain (same host or parent host), exact.
+    --accents               Accent-sensitive search ('societe' no longer finds 'Société').
+    --regex                 The -f/-d pattern is a regular expression (e.g. '^VPN-|\.gouv\.qc\.ca$').
+
+Non-interactive selection (scripts): -- fails if empty (code 2) or ambiguous (code 3)
+    --index <N>             Select the Nth entry of the list. The ranking then ignores recent use:
+                            the same N picks the same entry from one run to the next.
+    --first                 Select the first (most relevant) entry.
+    --exact <title>         Select the entry whose title is exactly <title>."#;
 
 #[derive(Debug)]
 pub struct LangStrings {
 	pub err_opt_l_1fois: &'static str,
 	pub err_opt_l_int: &'static str,
 	pub err_opt_l_bound: &'static str,
+	pub err_select_1fois: &'static str,
+	pub err_index_int: &'static str,
+	pub err_bad_field: &'static str,
+	pub err_bad_type: &'static str,
+	pub err_type_add: &'static str,
+	pub err_bad_tag: &'static str,
+	pub err_bad_folder: &'static str,
+	pub err_filter_cmd: &'static str,
+	pub err_bad_column: &'static str,
+	pub err_bad_sort: &'static str,
+	pub err_list_opt: &'static str,
+	pub err_bad_date: &'static str,
+	pub err_expiry_cmd: &'static str,
+	pub err_older_than: &'static str,
+	pub err_to_extract: &'static str,
+	pub err_bad_url: &'static str,
+	pub err_bad_match: &'static str,
+	pub err_match_url: &'static str,
+	pub err_bad_field_name: &'static str,
+	pub err_field_reserved: &'static str,
+	pub err_secret_field: &'static str,
+	pub err_field_cmd: &'static str,
+	pub err_secret_builtin: &'static str,
+	pub err_secret_type: &'static str,
+	pub err_field_type: &'static str,
+	pub err_update_add: &'static str,
+	pub err_attach_read: &'static str,
+	pub err_attach_big: &'static str,
+	pub err_attach_total: &'static str,
+	pub err_attach_corrupt: &'static str,
+	pub err_extract: &'static str,
+	pub err_days_int: &'static str,
 	pub manque_args: &'static str,
 	pub err_bad_arg: &'static str,
 	pub err_valid_args: &'static str,
@@ -91,18 +315,123 @@ pub struct LangStrings {
 	pub err_err: &'static str,
 	pub err_keyboard: &'static str,
 	pub err_index_too_big: &'static str,
+	pub err_bad_selection: &'static str,
+	pub err_ambiguous: &'static str,
+	pub err_no_field: &'static str,
 	pub err_interne: &'static str,
+	pub err_regex: &'static str,
+	pub err_regex_complex: &'static str,
 	pub comm_mut_excl: &'static str,
 	pub mdp_gen_str: &'static str,
 	pub enter_encryp_pw: &'static str,
+	pub add_pw_prompt: &'static str,
+	pub add_pw_confirm: &'static str,
+	pub err_pw_mismatch: &'static str,
+	pub err_pw_empty: &'static str,
+	pub err_separator: &'static str,
+	pub err_pw_vault: &'static str,
+	pub warn_pw_argv: &'static str,
+	pub warn_pw_ascii: &'static str,
+	pub warn_secret_argv: &'static str,
+	pub warn_type_secret_argv: &'static str,
+	pub err_pw_stdin: &'static str,
+	pub err_wizard_tty: &'static str,
+	pub wiz_title: &'static str,
+	pub wiz_user: &'static str,
+	pub wiz_url: &'static str,
+	pub wiz_notes: &'static str,
+	pub wiz_tags: &'static str,
+	pub wiz_folder: &'static str,
+	pub wiz_expiry: &'static str,
+	pub wiz_pw_choice: &'static str,
+	pub wiz_summary: &'static str,
+	pub wiz_confirm: &'static str,
+	pub wiz_type: &'static str,
+	pub wiz_secret: &'static str,
+	pub wiz_otp: &'static str,
+	pub otp_prompt: &'static str,
+	pub otp_choose: &'static str,
+	pub otp_to_set: &'static str,
+	pub otp_none: &'static str,
+	pub otp_code: &'static str,
+	pub otp_code_hotp: &'static str,
+	pub otp_saved: &'static str,
+	pub otp_removed: &'static str,
+	pub err_otp_invalid: &'static str,
+	pub import_prompt: &'static str,
+	pub err_import_line: &'static str,
+	pub import_attached: &'static str,
+	pub import_new: &'static str,
+	pub import_done: &'static str,
+	pub index_to_qr: &'static str,
+	pub err_qr_too_long: &'static str,
 	pub find_header: &'static str,
 	pub find_no_file: &'static str,
+	pub url_header: &'static str,
+	pub url_no_match: &'static str,
 	pub no_match: &'static str,
 	pub index_to_clip: &'static str,
+	pub index_to_show: &'static str,
+	pub reveal_hint: &'static str,
+	pub picker_help: &'static str,
+	pub picker_help_multi: &'static str,
+	pub picker_filter: &'static str,
 	pub del_header: &'static str,
 	pub del_no_file: &'static str,
+	pub get_no_file: &'static str,
 	pub index_to_del: &'static str,
 	pub del_success: &'static str,
+	pub trash_header: &'static str,
+	pub trash_empty: &'static str,
+	pub trash_deleted_on: &'static str,
+	pub index_to_restore: &'static str,
+	pub restore_success: &'static str,
+	pub purge_confirm: &'static str,
+	pub purge_success: &'static str,
+	pub undo_success: &'static str,
+	pub undo_empty: &'static str,
+	pub err_undo_conflict: &'static str,
+	pub log_empty: &'static str,
+	pub tags_empty: &'static str,
+	pub folders_empty: &'static str,
+	pub folder_none: &'static str,
+	pub list_empty: &'static str,
+	pub list_count: &'static str,
+	pub due_none: &'static str,
+	pub due_overdue: &'static str,
+	pub due_soon: &'static str,
+	pub warn_overdue: &'static str,
+	pub expiry_set: &'static str,
+	pub expiry_removed: &'static str,
+	pub attach_saved: &'static str,
+	pub attach_replace: &'static str,
+	pub detach_done: &'static str,
+	pub attach_empty: &'static str,
+	pub attach_none_named: &'static str,
+	pub extract_overwrite: &'static str,
+	pub extract_done: &'static str,
+	pub field_prompt: &'static str,
+	pub field_saved: &'static str,
+	pub field_removed: &'static str,
+	pub bytes: &'static str,
+	pub index_to_expire: &'static str,
+	pub index_to_attach: &'static str,
+	pub index_to_detach: &'static str,
+	pub index_to_field: &'static str,
+	pub dup_exists: &'static str,
+	pub dup_choice: &'static str,
+	pub dup_updated: &'static str,
+	pub dup_refused: &'static str,
+	pub op_cancel: &'static str,
+	pub dedupe_none: &'static str,
+	pub dedupe_group: &'static str,
+	pub dedupe_confirm: &'static str,
+	pub dedupe_keep: &'static str,
+	pub dedupe_merged: &'static str,
+	pub del_confirm: &'static str,
+	pub del_cancel: &'static str,
+	pub yes_no: &'static str,
+	pub yes_answers: &'static str,
 	pub add_new_save: &'static str,
 	pub usage: &'static str,
 	pub options: &'static str,
@@ -114,9 +443,41 @@ pub const LANG_FR: LangStrings = LangStrings {
 	err_opt_l_1fois: "L'option -l/--long ne peut être spécifiée qu'une seule fois.",
 	err_opt_l_int: "La longueur doit être un nombre entier valide.",
 	err_opt_l_bound: "La longueur doit être comprise entre 8 et 32 caractères.",
+	err_select_1fois: "Une seule option de sélection (--index, --first, --exact) est permise.",
+	err_index_int: "L'index doit être un nombre entier plus grand que 0.",
+	err_bad_field: "Champ '{1}' inconnu. Choix: {2}.",
+	err_bad_type: "Type '{1}' inconnu. Choix: {2}.",
+	err_type_add: "--type: seulement avec -a/--add.",
+	err_bad_tag: "--tag: étiquette invalide '{1}' (vide ou avec une virgule).",
+	err_bad_folder: "--folder: chemin de dossier invalide '{1}'.",
+	err_filter_cmd: "--tag/--folder: seulement avec une recherche (-f, -d, -g...) ou une création (-a, -n).",
+	err_bad_column: "--columns: colonne inconnue '{1}'. Choix: {2}.",
+	err_bad_sort: "--sort: tri inconnu '{1}'. Choix: {2}.",
+	err_list_opt: "--columns/--sort/--reverse/--tree: seulement avec --list.",
+	err_bad_date: "Date invalide '{1}': format AAAA-MM-JJ attendu.",
+	err_expiry_cmd: "--expires/--max-age: seulement avec -a, -n ou --set-expiry.",
+	err_older_than: "--older-than: seulement avec --empty-trash ou --list.",
+	err_to_extract: "--to: seulement avec --extract.",
+	err_bad_url: "URL invalide '{1}'.",
+	err_bad_match: "--match: degré inconnu '{1}'. Choix: {2}.",
+	err_match_url: "--match: seulement avec --url.",
+	err_bad_field_name: "Nom de champ invalide '{1}' (vide, ou avec '=').",
+	err_field_reserved: "'{1}' est un champ prédéfini: choisissez un autre nom.",
+	err_secret_field: "--secret: seulement avec --set-field.",
+	err_field_cmd: "--field: seulement avec -g/--get et --qr.",
+	err_secret_builtin: "--secret: '{1}' est un champ prédéfini, jamais masqué.",
+	err_secret_type: "--secret: '{1}' est un champ de type, masqué ou non selon le type.",
+	err_field_type: "'{1}' n'est pas un champ du type {2} de '{3}'.",
+	err_update_add: "--update: seulement avec -a ou -n.",
+	err_attach_read: "Lecture impossible de '{1}': {2}",
+	err_attach_big: "'{1}' est trop gros pour être joint (maximum {2} octets).",
+	err_attach_total: "Les pièces jointes de '{1}' dépasseraient {2} octets.",
+	err_attach_corrupt: "La pièce jointe '{1}' est illisible (base64 invalide).",
+	err_extract: "Extraction impossible de '{1}': {2}",
+	err_days_int: "Le nombre de jours doit être un entier valide.",
 	manque_args: "Arguments nécessaire manquant !!",
 	err_bad_arg: "Argument non reconnu ou mal placé",
-	err_valid_args: "La ligne de commande doit commencer par une option valide:\n\t\t==> (-f/--find, -d/--del, -a/--add, -n/--new, ou -l/--long) ou être vide.",
+	err_valid_args: "La ligne de commande doit commencer par une option valide:\n\t\t==> (-f/--find, -d/--del, -g/--get, -a/--add, -n/--new, ou -l/--long) ou être vide.",
 	err_cli: "Erreur de ligne de commande",
 	err_which: "Erreur de la command which.",
 	err_no_ssl: "La commande 'openssl' est inaccessible.",
@@ -126,20 +487,125 @@ pub const LANG_FR: LangStrings = LangStrings {
 	err_err: "Erreur:",
 	err_keyboard: "Erreur lors de la saisi au clavier.",
 	err_index_too_big: "Erreur : '{1}' est plus grand que le nombre d'entrée.",
+	err_bad_selection: "Erreur : sélection '{1}' invalide.",
+	err_ambiguous: "Sélection ambiguë: {1} entrées correspondent.",
+	err_no_field: "L'entrée '{1}' n'a pas de champ '{2}'.",
 	err_interne: "Erreur interne: Commande non définie.",
+	err_regex: "Expression régulière invalide '{1}' (position {2}).",
+	err_regex_complex: "Expression régulière trop complexe '{1}'.",
 	comm_mut_excl: "Les commandes sont mutuellement exclusives.",
 	mdp_gen_str: "Mot de passe généré:",
 	enter_encryp_pw: "Entrez le mot de passe d'encryption : ",
+	add_pw_prompt: "Mot de passe de l'entrée: ",
+	add_pw_confirm: "Confirmez le mot de passe: ",
+	err_pw_mismatch: "Les mots de passe ne correspondent pas.",
+	err_pw_empty: "Le mot de passe est vide.",
+	err_separator: "Le titre et le mot de passe ne peuvent contenir '∫∆∫', '∫≡∫' ni saut de ligne.",
+	err_pw_vault: "'{1}' ressemble au fichier mdp, pas à un mot de passe. Pour un autre fichier, placez une option avant (ex. 'mdp -a gmail --type login {1}'); pour un tel mot de passe, omettez-le pour qu'il soit demandé ou utilisez --password-stdin.",
+	warn_pw_argv: "⚠️ Un mot de passe sur la ligne de commande reste dans l'historique et 'ps'. Préférez '-a <description>' seul ou --password-stdin.",
+	warn_pw_ascii: "⚠️ Les caractères non ASCII (accents...) ont été ignorés: le mot de passe ne les contient pas.",
+	warn_secret_argv: "⚠️ Une valeur secrète sur la ligne de commande reste dans l'historique et 'ps'. Préférez '--set-field <motif> <nom> --secret' seul.",
+	warn_type_secret_argv: "⚠️ Une valeur secrète sur la ligne de commande reste dans l'historique et 'ps'. Préférez '--set-field <motif> <nom>' seul: elle sera demandée masquée.",
+	err_pw_stdin: "--password-stdin: seulement avec -a/--add <description>, sans mot de passe.",
+	err_wizard_tty: "L'assistant (-a sans description) demande un terminal; utilisez -a <description>.",
+	wiz_title: "Titre: ",
+	wiz_user: "Utilisateur (optionnel): ",
+	wiz_url: "URL (optionnel): ",
+	wiz_notes: "Notes (optionnel): ",
+	wiz_tags: "Étiquettes, séparées par des virgules (optionnel): ",
+	wiz_folder: "Dossier, ex. travail/aws (optionnel): ",
+	wiz_expiry: "Expiration: date AAAA-MM-JJ ou âge maximal en jours (optionnel): ",
+	wiz_pw_choice: "Mot de passe: 1) générer ({1} caractères)  2) le saisir [1]: ",
+	wiz_summary: "--- Nouvelle entrée:",
+	wiz_confirm: "Enregistrer cette entrée?",
+	wiz_type: "Type ({1}) [login]: ",
+	wiz_secret: "{1} (masqué): ",
+	wiz_otp: "Code 2FA: URI otpauth:// ou secret base32 (optionnel, masqué): ",
+	otp_prompt: "URI otpauth:// ou secret base32 (vide pour retirer): ",
+	otp_choose: "De quelle entrée voulez-vous le code? ",
+	otp_to_set: "À quelle entrée associer le secret 2FA? ",
+	otp_none: "--- Aucune entrée avec un code 2FA ne correspond à '{1}'.",
+	otp_code: "--- Code de '{1}': {2} (encore {3} s)",
+	otp_code_hotp: "--- Code de '{1}': {2} (compteur {3})",
+	otp_saved: "--- Secret 2FA enregistré pour '{1}'.",
+	otp_removed: "--- Secret 2FA retiré de '{1}'.",
+	err_otp_invalid: "Secret 2FA illisible pour '{1}': URI otpauth:// ou secret base32 attendu.",
+	import_prompt: "Collez les URI otpauth:// ou otpauth-migration://, une par ligne (ligne vide pour terminer):",
+	err_import_line: "Ligne {1} illisible: URI otpauth:// ou otpauth-migration:// attendue.",
+	import_attached: "--- Code 2FA ajouté à l'entrée '{1}'.",
+	import_new: "--- Nouvelle entrée '{1}' ({2}) avec code 2FA.",
+	import_done: "--- {1} compte(s) 2FA importé(s).",
+	index_to_qr: "Quelle entrée afficher en code QR? ",
+	err_qr_too_long: "Trop long pour un code QR ({1} octets).",
 	find_header: "Tentative de trouver le motif '{1}' dans le fichier '{2}'.",
 	find_no_file: "Le fichier '{1}' n'existe pas. Pas de recherche possible.",
+	url_header: "Recherche des entrées de l'adresse '{1}' dans le fichier '{2}'.",
+	url_no_match: "Aucune entrée pour le domaine de '{1}'.",
 	no_match: "Rien trouvé pour le motif '{1}'",
 	index_to_clip: "Quelle entrée ira vers le presse-papier ? ",
+	index_to_show: "Quel mot de passe désirez-vous afficher ? ",
+	reveal_hint: "(?N pour afficher le mot de passe N) ",
+	picker_help: "↑/↓: choisir   Entrée: valider   Esc: annuler   Tab: afficher le mot de passe",
+	picker_help_multi: "↑/↓: choisir   Espace: marquer   Entrée: valider   Esc: annuler   Tab: afficher le mot de passe",
+	picker_filter: "Filtre:",
 	del_header: "Recherche du motif '{1}' dans '{2}' pour suppression.",
 	del_no_file: "Le fichier '{1}' n'existe pas. Pas de suppresion possible.",
-	index_to_del: "Quelle entrée désirez-vous supprimer ? ",
-	del_success: "-- {1} -- Supprimé avec succès.",
+	get_no_file: "Le fichier '{1}' n'existe pas.",
+	index_to_del: "Quelle(s) entrée(s) désirez-vous supprimer ? (ex. 1,3,5-7 ou tout) ",
+	del_success: "-- {1} -- Déplacé vers la corbeille.",
+	trash_header: "Corbeille de '{1}':",
+	trash_empty: "\t------ La corbeille est vide.",
+	trash_deleted_on: "supprimé le",
+	index_to_restore: "Quelle(s) entrée(s) désirez-vous restaurer ? (ex. 1,3,5-7 ou tout) ",
+	restore_success: "-- {1} -- Restauré avec succès.",
+	purge_confirm: "Détruire définitivement {1} entrée(s) de la corbeille ?",
+	purge_success: "--- {1} entrée(s) détruite(s) définitivement.",
+	undo_success: "--- Annulé: {1} du {2} ({3}).",
+	undo_empty: "--- Rien à annuler: le journal est vide.",
+	err_undo_conflict: "Impossible d'annuler {1} du {2}: l'entrée '{3}' a changé depuis. Rien n'a été annulé.",
+	log_empty: "--- Le journal est vide.",
+	tags_empty: "--- Aucune étiquette.",
+	folders_empty: "--- Aucun dossier.",
+	folder_none: "(sans dossier)",
+	list_empty: "--- Aucune entrée.",
+	list_count: "--- Entrées: {1}.",
+	due_none: "--- Aucune entrée à renouveler d'ici {1} jours.",
+	due_overdue: "expirée depuis {1} j",
+	due_soon: "expire dans {1} j",
+	warn_overdue: "⚠️  Entrées à renouveler, échéance dépassée: {1} (voir --due).",
+	expiry_set: "--- Échéance de '{1}': {2}.",
+	expiry_removed: "--- '{1}' n'expire plus.",
+	attach_saved: "--- '{1}' joint à '{2}' ({3} octets).",
+	attach_replace: "'{1}' est déjà joint à cette entrée. Le remplacer?",
+	detach_done: "--- '{1}' retiré de '{2}'.",
+	attach_empty: "--- Aucune pièce jointe.",
+	attach_none_named: "--- Aucune entrée '{2}' n'a de pièce jointe '{1}'.",
+	extract_overwrite: "'{1}' existe déjà. L'écraser?",
+	extract_done: "--- '{1}' extrait vers '{2}' ({3} octets, permissions 0600).",
+	field_prompt: "Valeur de '{1}' (vide pour retirer le champ): ",
+	field_saved: "--- Champ '{1}' enregistré pour '{2}'.",
+	field_removed: "--- Champ '{1}' retiré de '{2}'.",
+	bytes: "octets",
+	index_to_expire: "De quelle entrée fixer l'échéance? ",
+	index_to_attach: "À quelle entrée joindre le fichier? ",
+	index_to_detach: "De quelle entrée retirer la pièce jointe? ",
+	index_to_field: "Dans quelle entrée fixer le champ? ",
+	dup_exists: "--- Une entrée nommée '{1}' existe déjà.",
+	dup_choice: "1) la mettre à jour  2) ajouter quand même sous '{1}'  3) annuler [3]: ",
+	dup_updated: "--- Entrée '{1}' mise à jour.",
+	dup_refused: "--- Rien n'a été enregistré: --yes ne remplace pas une entrée existante; ajoutez --update pour la mettre à jour.",
+	op_cancel: "--- Opération annulée.",
+	dedupe_none: "--- Aucun doublon.",
+	dedupe_group: "--- {1} entrées nommées '{2}':",
+	dedupe_confirm: "Mêmes mots de passe: les fusionner en une seule entrée?",
+	dedupe_keep: "Quel mot de passe garder? ",
+	dedupe_merged: "--- '{1}': {2} entrées fusionnées en une seule.",
+	del_confirm: "Supprimer {1} entrée(s) ?",
+	del_cancel: "--- Suppression annulée.",
+	yes_no: "(o/N)",
+	yes_answers: "o,oui,y,yes",
 	add_new_save: "--- Sauvegarde de '{1}' dans le fichier : '{2}'",
-	usage: "[-f <motif>|-d <motif>|-n <desc>|-a <desc> <password>] [-l <longueur>] [fichier mdp]",
+	usage: "[-f <motif>|-d <motif>|-g <nom>|-n <desc>|-a [<desc> [--password-stdin]]] [-l <longueur>] [-s] [-y] [--accents] [--regex] [--index N|--first|--exact <titre>] [fichier mdp]",
 	options: OPTIONS_FR,
 	ver: "version",
 	ver_desc: "Gestion de mots de passe en CLI, version",
@@ -149,9 +615,41 @@ pub const LANG_ES: LangStrings = LangStrings {
 	err_opt_l_1fois: "La opción -l/--long solo se puede especificar una vez.",
 	err_opt_l_int: "La longitud debe ser un número entero válido.",
 	err_opt_l_bound: "La longitud debe estar entre 8 y 32 caracteres.",
+	err_select_1fois: "Solo se permite una opción de selección (--index, --first, --exact).",
+	err_index_int: "El índice debe ser un número entero mayor que 0.",
+	err_bad_field: "Campo '{1}' desconocido. Opciones: {2}.",
+	err_bad_type: "Tipo '{1}' desconocido. Opciones: {2}.",
+	err_type_add: "--type: solo con -a/--add.",
+	err_bad_tag: "--tag: etiqueta no válida '{1}' (vacía o con una coma).",
+	err_bad_folder: "--folder: ruta de carpeta no válida '{1}'.",
+	err_filter_cmd: "--tag/--folder: solo con una búsqueda (-f, -d, -g...) o una creación (-a, -n).",
+	err_bad_column: "--columns: columna desconocida '{1}'. Opciones: {2}.",
+	err_bad_sort: "--sort: orden desconocido '{1}'. Opciones: {2}.",
+	err_list_opt: "--columns/--sort/--reverse/--tree: solo con --list.",
+	err_bad_date: "Fecha no válida '{1}': se espera el formato AAAA-MM-DD.",
+	err_expiry_cmd: "--expires/--max-age: solo con -a, -n o --set-expiry.",
+	err_older_than: "--older-than: solo con --empty-trash o --list.",
+	err_to_extract: "--to: solo con --extract.",
+	err_bad_url: "URL no válida '{1}'.",
+	err_bad_match: "--match: grado desconocido '{1}'. Opciones: {2}.",
+	err_match_url: "--match: solo con --url.",
+	err_bad_field_name: "Nombre de campo no válido '{1}' (vacío, o con '=').",
+	err_field_reserved: "'{1}' es un campo predefinido: elija otro nombre.",
+	err_secret_field: "--secret: solo con --set-field.",
+	err_field_cmd: "--field: solo con -g/--get y --qr.",
+	err_secret_builtin: "--secret: '{1}' es un campo predefinido, nunca oculto.",
+	err_secret_type: "--secret: '{1}' es un campo de tipo, oculto o no según el tipo.",
+	err_field_type: "'{1}' no es un campo del tipo {2} de '{3}'.",
+	err_update_add: "--update: solo con -a o -n.",
+	err_attach_read: "No se puede leer '{1}': {2}",
+	err_attach_big: "'{1}' es demasiado grande para adjuntarlo (máximo {2} bytes).",
+	err_attach_total: "Los adjuntos de '{1}' superarían {2} bytes.",
+	err_attach_corrupt: "El adjunto '{1}' es ilegible (base64 no válido).",
+	err_extract: "No se puede extraer '{1}': {2}",
+	err_days_int: "El número de días debe ser un entero válido.",
 	manque_args: "Argumentos necesarios faltantes !!",
 	err_bad_arg: "Argumento no reconocido o mal colocado",
-	err_valid_args: "La línea de comandos debe comenzar con una opción válida:\n\t\t==> (-f/--find, -d/--del, -a/--add, -n/--new, o -l/--long) o estar vacía.",
+	err_valid_args: "La línea de comandos debe comenzar con una opción válida:\n\t\t==> (-f/--find, -d/--del, -g/--get, -a/--add, -n/--new, o -l/--long) o estar vacía.",
 	err_cli: "Error de línea de comandos",
 	err_which: "Error del comando 'which'.",
 	err_no_ssl: "El comando 'openssl' es inaccesible.",
@@ -161,20 +659,125 @@ pub const LANG_ES: LangStrings = LangStrings {
 	err_err: "Error:",
 	err_keyboard: "Error durante la entrada por teclado.",
 	err_index_too_big: "Error: '{1}' es mayor que el número de entradas.",
+	err_bad_selection: "Error: selección '{1}' inválida.",
+	err_ambiguous: "Selección ambigua: {1} entradas coinciden.",
+	err_no_field: "La entrada '{1}' no tiene campo '{2}'.",
 	err_interne: "Error interno: Comando no definido.",
+	err_regex: "Expresión regular inválida '{1}' (posición {2}).",
+	err_regex_complex: "Expresión regular demasiado compleja '{1}'.",
 	comm_mut_excl: "Los comandos son mutuamente excluyentes.",
 	mdp_gen_str: "Contraseña generada:",
 	enter_encryp_pw: "Ingrese la contraseña de cifrado: ",
+	add_pw_prompt: "Contraseña de la entrada: ",
+	add_pw_confirm: "Confirme la contraseña: ",
+	err_pw_mismatch: "Las contraseñas no coinciden.",
+	err_pw_empty: "La contraseña está vacía.",
+	err_separator: "El título y la contraseña no pueden contener '∫∆∫', '∫≡∫' ni saltos de línea.",
+	err_pw_vault: "'{1}' parece el archivo mdp, no una contraseña. Para otro archivo, ponga una opción antes (ej. 'mdp -a gmail --type login {1}'); para tal contraseña, omítala para que se pida o use --password-stdin.",
+	warn_pw_argv: "⚠️ Una contraseña en la línea de comandos queda en el historial y en 'ps'. Prefiera '-a <descripción>' solo o --password-stdin.",
+	warn_pw_ascii: "⚠️ Se ignoraron los caracteres no ASCII (acentos...): la contraseña no los contiene.",
+	warn_secret_argv: "⚠️ Un valor secreto en la línea de comandos queda en el historial y en 'ps'. Prefiera '--set-field <patrón> <nombre> --secret' solo.",
+	warn_type_secret_argv: "⚠️ Un valor secreto en la línea de comandos queda en el historial y en 'ps'. Prefiera '--set-field <patrón> <nombre>' solo: se pedirá oculto.",
+	err_pw_stdin: "--password-stdin: solo con -a/--add <descripción>, sin contraseña.",
+	err_wizard_tty: "El asistente (-a sin descripción) requiere una terminal; use -a <descripción>.",
+	wiz_title: "Título: ",
+	wiz_user: "Usuario (opcional): ",
+	wiz_url: "URL (opcional): ",
+	wiz_notes: "Notas (opcional): ",
+	wiz_tags: "Etiquetas, separadas por comas (opcional): ",
+	wiz_folder: "Carpeta, p. ej. trabajo/aws (opcional): ",
+	wiz_expiry: "Vencimiento: fecha AAAA-MM-DD o edad máxima en días (opcional): ",
+	wiz_pw_choice: "Contraseña: 1) generar ({1} caracteres)  2) escribirla [1]: ",
+	wiz_summary: "--- Nueva entrada:",
+	wiz_confirm: "¿Guardar esta entrada?",
+	wiz_type: "Tipo ({1}) [login]: ",
+	wiz_secret: "{1} (oculto): ",
+	wiz_otp: "Código 2FA: URI otpauth:// o secreto base32 (opcional, oculto): ",
+	otp_prompt: "URI otpauth:// o secreto base32 (vacío para quitar): ",
+	otp_choose: "¿De qué entrada quiere el código? ",
+	otp_to_set: "¿A qué entrada asociar el secreto 2FA? ",
+	otp_none: "--- Ninguna entrada con código 2FA coincide con '{1}'.",
+	otp_code: "--- Código de '{1}': {2} (quedan {3} s)",
+	otp_code_hotp: "--- Código de '{1}': {2} (contador {3})",
+	otp_saved: "--- Secreto 2FA guardado para '{1}'.",
+	otp_removed: "--- Secreto 2FA quitado de '{1}'.",
+	err_otp_invalid: "Secreto 2FA ilegible para '{1}': se espera una URI otpauth:// o un secreto base32.",
+	import_prompt: "Pegue las URI otpauth:// u otpauth-migration://, una por línea (línea vacía para terminar):",
+	err_import_line: "Línea {1} ilegible: se espera una URI otpauth:// u otpauth-migration://.",
+	import_attached: "--- Código 2FA añadido a la entrada '{1}'.",
+	import_new: "--- Nueva entrada '{1}' ({2}) con código 2FA.",
+	import_done: "--- {1} cuenta(s) 2FA importada(s).",
+	index_to_qr: "¿Qué entrada mostrar como código QR? ",
+	err_qr_too_long: "Demasiado largo para un código QR ({1} bytes).",
 	find_header: "Intentando buscar el patrón '{1}' en el archivo '{2}'.",
 	find_no_file: "El archivo '{1}' no existe. Búsqueda no posible.",
+	url_header: "Búsqueda de las entradas de la dirección '{1}' en el archivo '{2}'.",
+	url_no_match: "Ninguna entrada para el dominio de '{1}'.",
 	no_match: "No se encontró nada para el patrón '{1}'",
 	index_to_clip: "¿Qué entrada desea copiar al portapapeles? ",
+	index_to_show: "¿Qué contraseña desea mostrar? ",
+	reveal_hint: "(?N para mostrar la contraseña N) ",
+	picker_help: "↑/↓: elegir   Intro: validar   Esc: cancelar   Tab: mostrar la contraseña",
+	picker_help_multi: "↑/↓: elegir   Espacio: marcar   Intro: validar   Esc: cancelar   Tab: mostrar la contraseña",
+	picker_filter: "Filtro:",
 	del_header: "Buscando el patrón '{1}' en '{2}' para su eliminación.",
 	del_no_file: "El archivo '{1}' no existe. No es posible realizar la eliminación.",
-	index_to_del: "¿Qué entrada desea eliminar? ",
-	del_success: "-- {1} -- Eliminado con éxito.",
+	get_no_file: "El archivo '{1}' no existe.",
+	index_to_del: "¿Qué entrada(s) desea eliminar? (ej. 1,3,5-7 o todo) ",
+	del_success: "-- {1} -- Movido a la papelera.",
+	trash_header: "Papelera de '{1}':",
+	trash_empty: "\t------ La papelera está vacía.",
+	trash_deleted_on: "eliminado el",
+	index_to_restore: "¿Qué entrada(s) desea restaurar? (ej. 1,3,5-7 o todo) ",
+	restore_success: "-- {1} -- Restaurado con éxito.",
+	purge_confirm: "¿Destruir definitivamente {1} entrada(s) de la papelera?",
+	purge_success: "--- {1} entrada(s) destruida(s) definitivamente.",
+	undo_success: "--- Anulado: {1} del {2} ({3}).",
+	undo_empty: "--- Nada que anular: el registro está vacío.",
+	err_undo_conflict: "No se puede anular {1} del {2}: la entrada '{3}' cambió desde entonces. No se anuló nada.",
+	log_empty: "--- El registro está vacío.",
+	tags_empty: "--- Ninguna etiqueta.",
+	folders_empty: "--- Ninguna carpeta.",
+	folder_none: "(sin carpeta)",
+	list_empty: "--- Ninguna entrada.",
+	list_count: "--- Entradas: {1}.",
+	due_none: "--- Ninguna entrada que renovar en los próximos {1} días.",
+	due_overdue: "vencida hace {1} d",
+	due_soon: "vence en {1} d",
+	warn_overdue: "⚠️  Entradas que renovar, vencimiento superado: {1} (ver --due).",
+	expiry_set: "--- Vencimiento de '{1}': {2}.",
+	expiry_removed: "--- '{1}' ya no vence.",
+	attach_saved: "--- '{1}' adjuntado a '{2}' ({3} bytes).",
+	attach_replace: "'{1}' ya está adjuntado a esta entrada. ¿Reemplazarlo?",
+	detach_done: "--- '{1}' quitado de '{2}'.",
+	attach_empty: "--- Ningún adjunto.",
+	attach_none_named: "--- Ninguna entrada '{2}' tiene un adjunto '{1}'.",
+	extract_overwrite: "'{1}' ya existe. ¿Sobrescribirlo?",
+	extract_done: "--- '{1}' extraído a '{2}' ({3} bytes, permisos 0600).",
+	field_prompt: "Valor de '{1}' (vacío para quitar el campo): ",
+	field_saved: "--- Campo '{1}' guardado para '{2}'.",
+	field_removed: "--- Campo '{1}' quitado de '{2}'.",
+	bytes: "bytes",
+	index_to_expire: "¿De qué entrada fijar el vencimiento? ",
+	index_to_attach: "¿A qué entrada adjuntar el archivo? ",
+	index_to_detach: "¿De qué entrada quitar el adjunto? ",
+	index_to_field: "¿En qué entrada fijar el campo? ",
+	dup_exists: "--- Ya existe una entrada llamada '{1}'.",
+	dup_choice: "1) actualizarla  2) añadir de todos modos como '{1}'  3) cancelar [3]: ",
+	dup_updated: "--- Entrada '{1}' actualizada.",
+	dup_refused: "--- No se guardó nada: --yes no reemplaza una entrada existente; añada --update para actualizarla.",
+	op_cancel: "--- Operación cancelada.",
+	dedupe_none: "--- Ningún duplicado.",
+	dedupe_group: "--- {1} entradas llamadas '{2}':",
+	dedupe_confirm: "Mismas contraseñas: ¿fusionarlas en una sola entrada?",
+	dedupe_keep: "¿Qué contraseña conservar? ",
+	dedupe_merged: "--- '{1}': {2} entradas fusionadas en una sola.",
+	del_confirm: "¿Eliminar {1} entrada(s)?",
+	del_cancel: "--- Eliminación cancelada.",
+	yes_no: "(s/N)",
+	yes_answers: "s,si,sí,y,yes",
 	add_new_save: "--- Guardando '{1}' en el archivo: '{2}'",
-	usage: "[-f <patrón>|-d <patrón>|-n <desc>|-a <desc> <contraseña>] [-l <longitud>] [archivo contras]",
+	usage: "[-f <patrón>|-d <patrón>|-g <nombre>|-n <desc>|-a [<desc> [--password-stdin]]] [-l <longitud>] [-s] [-y] [--accents] [--regex] [--index N|--first|--exact <título>] [archivo contras]",
 	options: OPTIONS_ES,
 	ver: "versión",
 	ver_desc: "Gestión de contraseñas en CLI, versión",
@@ -184,9 +787,41 @@ pub const LANG_EN: LangStrings = LangStrings {
 	err_opt_l_1fois: "The -l/--long option can only be specified once.",
 	err_opt_l_int: "The length must be a valid integer.",
 	err_opt_l_bound: "The length must be between 8 and 32 characters.",
+	err_select_1fois: "Only one selection option (--index, --first, --exact) is allowed.",
+	err_index_int: "The index must be an integer greater than 0.",
+	err_bad_field: "Unknown field '{1}'. Choices: {2}.",
+	err_bad_type: "Unknown type '{1}'. Choices: {2}.",
+	err_type_add: "--type: only with -a/--add.",
+	err_bad_tag: "--tag: invalid tag '{1}' (empty or containing a comma).",
+	err_bad_folder: "--folder: invalid folder path '{1}'.",
+	err_filter_cmd: "--tag/--folder: only with a search (-f, -d, -g...) or a creation (-a, -n).",
+	err_bad_column: "--columns: unknown column '{1}'. Choices: {2}.",
+	err_bad_sort: "--sort: unknown sort '{1}'. Choices: {2}.",
+	err_list_opt: "--columns/--sort/--reverse/--tree: only with --list.",
+	err_bad_date: "Invalid date '{1}': expected YYYY-MM-DD.",
+	err_expiry_cmd: "--expires/--max-age: only with -a, -n or --set-expiry.",
+	err_older_than: "--older-than: only with --empty-trash or --list.",
+	err_to_extract: "--to: only with --extract.",
+	err_bad_url: "Invalid URL '{1}'.",
+	err_bad_match: "--match: unknown level '{1}'. Choices: {2}.",
+	err_match_url: "--match: only with --url.",
+	err_bad_field_name: "Invalid field name '{1}' (empty, or containing '=').",
+	err_field_reserved: "'{1}' is a built-in field: choose another name.",
+	err_secret_field: "--secret: only with --set-field.",
+	err_field_cmd: "--field: only with -g/--get and --qr.",
+	err_secret_builtin: "--secret: '{1}' is a built-in field, never masked.",
+	err_secret_type: "--secret: '{1}' is a type field, masked or not by its type.",
+	err_field_type: "'{1}' is not a field of type {2} of '{3}'.",
+	err_update_add: "--update: only with -a or -n.",
+	err_attach_read: "Cannot read '{1}': {2}",
+	err_attach_big: "'{1}' is too large to attach (maximum {2} bytes).",
+	err_attach_total: "The attachments of '{1}' would exceed {2} bytes.",
+	err_attach_corrupt: "Attachment '{1}' is unreadable (invalid base64).",
+	err_extract: "Cannot extract '{1}': {2}",
+	err_days_int: "The number of days must be a valid integer.",
 	manque_args: "Missing required arguments !!",
 	err_bad_arg: "Unrecognized or misplaced argument",
-	err_valid_args: "The command line must start with a valid option:\n\t\t==> (-f/--find, -d/--del, -a/--add, -n/--new, or -l/--long) or be empty.",
+	err_valid_args: "The command line must start with a valid option:\n\t\t==> (-f/--find, -d/--del, -g/--get, -a/--add, -n/--new, or -l/--long) or be empty.",
 	err_cli: "Command line error",
 	err_which: "Error with the 'which' command.",
 	err_no_ssl: "The 'openssl' command is inaccessible.",
@@ -196,20 +831,125 @@ pub const LANG_EN: LangStrings = LangStrings {
 	err_err: "Error:",
 	err_keyboard: "Error during keyboard input.",
 	err_index_too_big: "Error: '{1}' is greater than the number of entries.",
+	err_bad_selection: "Error: invalid selection '{1}'.",
+	err_ambiguous: "Ambiguous selection: {1} entries match.",
+	err_no_field: "Entry '{1}' has no '{2}' field.",
 	err_interne: "Internal error: Command not defined.",
+	err_regex: "Invalid regular expression '{1}' (position {2}).",
+	err_regex_complex: "Regular expression too complex '{1}'.",
 	comm_mut_excl: "Commands are mutually exclusive.",
 	mdp_gen_str: "Generated password:",
 	enter_encryp_pw: "Enter the encryption password: ",
+	add_pw_prompt: "Entry password: ",
+	add_pw_confirm: "Confirm the password: ",
+	err_pw_mismatch: "The passwords do not match.",
+	err_pw_empty: "The password is empty.",
+	err_separator: "The title and password cannot contain '∫∆∫', '∫≡∫' or line breaks.",
+	err_pw_vault: "'{1}' looks like the password file, not a password. For another file, put an option before it (e.g. 'mdp -a gmail --type login {1}'); for such a password, leave it out to be prompted, or use --password-stdin.",
+	warn_pw_argv: "⚠️ A password on the command line stays in the shell history and 'ps'. Prefer '-a <description>' alone or --password-stdin.",
+	warn_pw_ascii: "⚠️ Non-ASCII characters (accents...) were ignored: the password does not contain them.",
+	warn_secret_argv: "⚠️ A secret value on the command line stays in the shell history and 'ps'. Prefer '--set-field <pattern> <name> --secret' alone.",
+	warn_type_secret_argv: "⚠️ A secret value on the command line stays in the shell history and 'ps'. Prefer '--set-field <pattern> <name>' alone: it will be prompted for, hidden.",
+	err_pw_stdin: "--password-stdin: only with -a/--add <description>, without a password.",
+	err_wizard_tty: "The wizard (-a without a description) needs a terminal; use -a <description>.",
+	wiz_title: "Title: ",
+	wiz_user: "Username (optional): ",
+	wiz_url: "URL (optional): ",
+	wiz_notes: "Notes (optional): ",
+	wiz_tags: "Tags, comma-separated (optional): ",
+	wiz_folder: "Folder, e.g. work/aws (optional): ",
+	wiz_expiry: "Expiry: YYYY-MM-DD date or maximum age in days (optional): ",
+	wiz_pw_choice: "Password: 1) generate ({1} characters)  2) type it [1]: ",
+	wiz_summary: "--- New entry:",
+	wiz_confirm: "Save this entry?",
+	wiz_type: "Type ({1}) [login]: ",
+	wiz_secret: "{1} (hidden): ",
+	wiz_otp: "2FA: otpauth:// URI or base32 secret (optional, hidden): ",
+	otp_prompt: "otpauth:// URI or base32 secret (empty to remove): ",
+	otp_choose: "Which entry's code do you want? ",
+	otp_to_set: "Which entry gets the 2FA secret? ",
+	otp_none: "--- No entry with a 2FA code matches '{1}'.",
+	otp_code: "--- Code for '{1}': {2} ({3} s left)",
+	otp_code_hotp: "--- Code for '{1}': {2} (counter {3})",
+	otp_saved: "--- 2FA secret saved for '{1}'.",
+	otp_removed: "--- 2FA secret removed from '{1}'.",
+	err_otp_invalid: "Unreadable 2FA secret for '{1}': expected an otpauth:// URI or a base32 secret.",
+	import_prompt: "Paste otpauth:// or otpauth-migration:// URIs, one per line (empty line to finish):",
+	err_import_line: "Unreadable line {1}: expected an otpauth:// or otpauth-migration:// URI.",
+	import_attached: "--- 2FA code added to entry '{1}'.",
+	import_new: "--- New entry '{1}' ({2}) with a 2FA code.",
+	import_done: "--- {1} 2FA account(s) imported.",
+	index_to_qr: "Which entry do you want as a QR code? ",
+	err_qr_too_long: "Too long for a QR code ({1} bytes).",
 	find_header: "Attempting to find the pattern '{1}' in file '{2}'.",
 	find_no_file: "File '{1}' does not exist. Search not possible.",
+	url_header: "Looking up entries for the address '{1}' in file '{2}'.",
+	url_no_match: "No entry for the domain of '{1}'.",
 	no_match: "No matches found for pattern '{1}'",
 	index_to_clip: "Which entry should go to the clipboard? ",
+	index_to_show: "Which password do you want to display? ",
+	reveal_hint: "(?N to reveal password N) ",
+	picker_help: "↑/↓: move   Enter: select   Esc: cancel   Tab: reveal password",
+	picker_help_multi: "↑/↓: move   Space: mark   Enter: select   Esc: cancel   Tab: reveal password",
+	picker_filter: "Filter:",
 	del_header: "Searching for pattern '{1}' in '{2}' for deletion.",
 	del_no_file: "The file '{1}' does not exist. Deletion is not possible.",
-	index_to_del: "Which entry do you want to delete? ",
-	del_success: "-- {1} -- Successfully deleted.",
+	get_no_file: "File '{1}' does not exist.",
+	index_to_del: "Which entry(ies) do you want to delete? (e.g. 1,3,5-7 or all) ",
+	del_success: "-- {1} -- Moved to the trash.",
+	trash_header: "Trash of '{1}':",
+	trash_empty: "\t------ The trash is empty.",
+	trash_deleted_on: "deleted on",
+	index_to_restore: "Which entry(ies) do you want to restore? (e.g. 1,3,5-7 or all) ",
+	restore_success: "-- {1} -- Successfully restored.",
+	purge_confirm: "Permanently destroy {1} entry(ies) from the trash?",
+	purge_success: "--- {1} entry(ies) permanently destroyed.",
+	undo_success: "--- Undone: {1} of {2} ({3}).",
+	undo_empty: "--- Nothing to undo: the journal is empty.",
+	err_undo_conflict: "Cannot undo {1} of {2}: entry '{3}' has changed since. Nothing was undone.",
+	log_empty: "--- The journal is empty.",
+	tags_empty: "--- No tags.",
+	folders_empty: "--- No folders.",
+	folder_none: "(no folder)",
+	list_empty: "--- No entries.",
+	list_count: "--- Entries: {1}.",
+	due_none: "--- No entries to rotate within {1} days.",
+	due_overdue: "overdue by {1} d",
+	due_soon: "due in {1} d",
+	warn_overdue: "⚠️  Entries overdue for rotation: {1} (see --due).",
+	expiry_set: "--- Expiry of '{1}': {2}.",
+	expiry_removed: "--- '{1}' no longer expires.",
+	attach_saved: "--- '{1}' attached to '{2}' ({3} bytes).",
+	attach_replace: "'{1}' is already attached to this entry. Replace it?",
+	detach_done: "--- '{1}' removed from '{2}'.",
+	attach_empty: "--- No attachments.",
+	attach_none_named: "--- No '{2}' entry has an attachment '{1}'.",
+	extract_overwrite: "'{1}' already exists. Overwrite it?",
+	extract_done: "--- '{1}' extracted to '{2}' ({3} bytes, mode 0600).",
+	field_prompt: "Value of '{1}' (empty to remove the field): ",
+	field_saved: "--- Field '{1}' saved for '{2}'.",
+	field_removed: "--- Field '{1}' removed from '{2}'.",
+	bytes: "bytes",
+	index_to_expire: "Which entry should the expiry apply to? ",
+	index_to_attach: "Which entry should the file be attached to? ",
+	index_to_detach: "Which entry should the attachment be removed from? ",
+	index_to_field: "Which entry should the field be set on? ",
+	dup_exists: "--- An entry named '{1}' already exists.",
+	dup_choice: "1) update it  2) add anyway as '{1}'  3) cancel [3]: ",
+	dup_updated: "--- Entry '{1}' updated.",
+	dup_refused: "--- Nothing was saved: --yes does not replace an existing entry; add --update to update it.",
+	op_cancel: "--- Operation cancelled.",
+	dedupe_none: "--- No duplicates found.",
+	dedupe_group: "--- {1} entries named '{2}':",
+	dedupe_confirm: "Same passwords: merge them into one entry?",
+	dedupe_keep: "Which password do you keep? ",
+	dedupe_merged: "--- '{1}': {2} entries merged into one.",
+	del_confirm: "Delete {1} entry(ies)?",
+	del_cancel: "--- Deletion cancelled.",
+	yes_no: "(y/N)",
+	yes_answers: "y,yes",
 	add_new_save: "--- Saving '{1}' to file: '{2}'",
-	usage: "[-f <pattern>|-d <pattern>|-n <desc>|-a <desc> <password>] [-l <length>] [pwd file]",
+	usage: "[-f <pattern>|-d <pattern>|-g <name>|-n <desc>|-a [<desc> [--password-stdin]]] [-l <length>] [-s] [-y] [--accents] [--regex] [--index N|--first|--exact <title>] [pwd file]",
 	options: OPTIONS_EN,
 	ver: "version",
 	ver_desc: "CLI password management, version",
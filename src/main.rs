@@ -1,5 +1,8 @@
 // main.rs
 
+// Le style du projet garde les `return` explicites et les `&'static str` dans les constantes.
+#![allow(clippy::needless_return, clippy::redundant_static_lifetimes)]
+
 mod locale;
 mod parse;
 mod generator;
@@ -7,14 +10,31 @@ mod clipboard;
 mod get_pw;
 mod openssl_cli;
 mod actions;
+mod entry;
+mod search;
+mod regex;
+mod picker;
+mod date;
+mod journal;
+mod otp;
+mod base64;
+mod qr;
+mod template;
+mod domain;
 
 use std::env;
+use std::io;
+use std::os::unix::io::AsRawFd;
 use std::process::Command;
 use std::path::Path;
 use std::process::ExitCode;
 // Importe les types nécessaires
 use locale::LangStrings;
-use parse::CommandsOptions;
+use entry::Entry;
+use search::Query;
+use regex::RegexError;
+use parse::{CommandsOptions, Selector};
+use actions::Outcome;
 use generator::gen_pass;
 use clipboard::send_to_clipboard;
 use openssl_cli::{decrypt_via_cli, encrypt_via_cli};
@@ -25,14 +45,61 @@ const PRG_NAME: &'static str = "mdp";
 const VERSION: &'static str = "2025-12-22";
 const DEFAULT_PW_LENGTH: usize = 12;
 const DEFAULT_FILENAME: &'static str = "mdp.bin";
+const EXIT_NOT_FOUND: u8 = 2;
+const EXIT_AMBIGUOUS: u8 = 3;
 
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct AppData {
 	app_locale: LangStrings,
 	app_pw_len: usize,
+	app_show_pw: bool,
+	app_selector: Selector,
+	app_assume_yes: bool,
+	app_update: bool,		// --update: -a/-n remplacent l'entrée de même titre
+	app_filters: search::Filters,	// --tag/--folder: étiquettes et dossier donnés aux entrées créées
+	app_expiry: parse::Expiry,		// --expires/--max-age: échéance des entrées créées ou modifiées
 	app_encryp_pass: String,
-	app_line_vec: Vec<String>,
+	app_entries: Vec<Entry>,
+	app_trash: Vec<Entry>,
+	app_journal: Vec<journal::Operation>,
+}
+
+impl AppData {
+	/// Lignes des entrées, puis de la corbeille.
+	fn entry_lines(&self) -> Vec<String> {
+		let mut lignes = entry::to_lines(&self.app_entries);
+		lignes.extend(entry::to_lines(&self.app_trash));
+		lignes
+	}
+
+	/// Lignes du fichier mdp: les entrées, la corbeille, puis le journal.
+	fn vault_lines(&self) -> Vec<String> {
+		let mut lignes = self.entry_lines();
+		lignes.extend(journal::to_lines(&self.app_journal));
+		lignes
+	}
+}
+
+// Nom de l'opération inscrite au journal, pour les commandes qui modifient le contenu.
+// Exclus volontairement:
+//  - l'usage (find, get, --otp): date d'utilisation et compteur HOTP ne s'annulent pas;
+//  - --empty-trash: les mots de passe détruits ne doivent survivre nulle part, journal compris.
+fn journal_op(command: &CommandsOptions) -> Option<&'static str> {
+	match command {
+		CommandsOptions::Delete(_) => Some("del"),
+		CommandsOptions::Add(_, _) | CommandsOptions::Wizard => Some("add"),
+		CommandsOptions::New(_) => Some("new"),
+		CommandsOptions::Undelete(_) => Some("undelete"),
+		CommandsOptions::Dedupe => Some("dedupe"),
+		CommandsOptions::OtpSet(_) => Some("otp"),
+		CommandsOptions::SetExpiry(_) => Some("expiry"),
+		CommandsOptions::Attach(_, _) => Some("attach"),
+		CommandsOptions::Detach(_, _) => Some("detach"),
+		CommandsOptions::SetField(_, _, _) => Some("field"),
+		CommandsOptions::ImportOtp => Some("import"),
+		_ => None,
+	}
 }
 
 pub fn command_exist(cmd: &str, locale: &LangStrings) -> bool {
@@ -43,8 +110,7 @@ pub fn command_exist(cmd: &str, locale: &LangStrings) -> bool {
 		.status()
 		.expect(locale.err_which);
 
-	if status.success() { return true; }
-	else { return false; }
+	status.success()
 }
 
 pub fn open_mdp_file() {
@@ -81,6 +147,12 @@ pub fn main() -> ExitCode {
 		}
 	};
 	let app_pw_len = config.password_length.unwrap_or(DEFAULT_PW_LENGTH);
+	let app_show_pw = config.show_passwords;
+	let app_selector = config.selector.clone();
+	let app_assume_yes = config.assume_yes;
+	let app_update = config.update;
+	let app_filters = config.filters.clone();
+	let app_expiry = config.expiry.clone();
 
 	match &config.command {		// Premier tri, ces commandes n'ont pas besoin de openssl
 		CommandsOptions::Help(message) => {
@@ -100,9 +172,29 @@ pub fn main() -> ExitCode {
 			else { println!("{} {pw}", app_locale.mdp_gen_str); }
 			return ExitCode::SUCCESS;
 		}
+		CommandsOptions::Wizard if !get_pw::is_tty(io::stdin().as_raw_fd()) => {
+			eprintln!("{}", app_locale.err_wizard_tty);
+			return ExitCode::FAILURE;
+		}
 		_ => { }
 	}
 
+	// ############################################################################
+	// Le motif de recherche est validé avant de demander le mot de passe
+	let query = match Query::parse(config.command.pattern().unwrap_or(""), config.search_options) {
+		Ok(q) => q.with_filters(config.filters.clone()),
+		Err(e) => {
+			let motif = config.command.pattern().unwrap_or("");
+			match e {
+				RegexError::Syntax(pos) => eprintln!("🛑 {}", app_locale.err_regex
+								.replace("{1}", motif)
+								.replace("{2}", &(pos + 1).to_string())),
+				RegexError::TooComplex => eprintln!("🛑 {}", app_locale.err_regex_complex.replace("{1}", motif)),
+			}
+			return ExitCode::FAILURE;
+		}
+	};
+
 	// ############################################################################
 	// Le reste des commandes font affaire avec openssl
 	if ! command_exist("openssl", &app_locale) {
@@ -110,6 +202,10 @@ pub fn main() -> ExitCode {
 		return ExitCode::FAILURE;
 	}
 
+	// ############################################################################
+	// Les chemins des pièces jointes (--attach, --to) restent relatifs au répertoire de départ
+	let repertoire_depart = env::current_dir().unwrap_or_default();
+
 	// ############################################################################
 	// Identification du path du fichier mdp et chdir le cas échéant.
 	let mut file_output = config.output_file.as_deref().unwrap_or(DEFAULT_FILENAME);
@@ -136,7 +232,7 @@ pub fn main() -> ExitCode {
 		}
 	}
 	else {
-		if let Err(_) = env::set_current_dir(exec_path) {
+		if env::set_current_dir(exec_path).is_err() {
 			eprintln!("{}", app_locale.err_cd_execpath);
 			return ExitCode::from(15);
 		}
@@ -148,13 +244,13 @@ pub fn main() -> ExitCode {
 	let mdp_file_exists = file_output_path.exists();
 	let mut app_encryp_pass = String::new();
 
-	let app_line_vec = if mdp_file_exists {
+	let lignes = if mdp_file_exists {
 		// Récupère la variable d'environnement "pass"
 		app_encryp_pass = match env::var("pass") {
 			Ok(v) => v,
-			Err(_) => get_pw!(app_locale.enter_encryp_pw),
+			Err(_) => actions::read_pw(app_locale.enter_encryp_pw, &app_locale),
 		};
-		decrypt_via_cli(&file_output, &app_encryp_pass)
+		decrypt_via_cli(file_output, &app_encryp_pass)
 									.unwrap_or_else(|e| {
 			eprintln!("{} {}", app_locale.err_err, e);
 			std::process::exit(20); // Arrêt immédiat si le déchiffrement échoue
@@ -162,23 +258,56 @@ pub fn main() -> ExitCode {
 	}
 	else { Vec::new() }; // Fichier inexistant = liste vide
 
-	let mut app_data = AppData {app_locale, app_pw_len, app_encryp_pass, app_line_vec};
+	// Le journal suit les entrées; les entrées supprimées portent leur date de suppression
+	// et vont à la corbeille.
+	let (lignes_journal, lignes): (Vec<String>, Vec<String>) = lignes.into_iter()
+		.partition(|ligne| journal::is_journal_line(ligne));
+	let app_journal = journal::parse_lines(lignes_journal.iter());
+	let (app_entries, app_trash): (Vec<Entry>, Vec<Entry>) = lignes.iter()
+		.map(|ligne| Entry::parse(ligne))
+		.partition(|e| e.get(entry::DELETED).is_none());
+
+	let mut app_data = AppData {app_locale, app_pw_len, app_show_pw, app_selector, app_assume_yes, app_update,
+								app_filters, app_expiry, app_encryp_pass, app_entries, app_trash, app_journal};
+	// Rappel des entrées à renouveler, sauf pour --due qui les liste
+	if !matches!(config.command, CommandsOptions::Due(_)) {
+		actions::warn_overdue(&app_data);
+	}
+	// Photo des entrées avant la commande, pour le journal
+	let lignes_avant = app_data.entry_lines();
 
 	let finalize_encryption = |data: &mut AppData| {
 		if !mdp_file_exists {
 			data.app_encryp_pass = match env::var("pass") {
 				Ok(v) => v,
 				// Utilise & pour emprunter la String au lieu de la déplacer
-				Err(_) => get_pw!(&data.app_locale.enter_encryp_pw),
+				Err(_) => actions::read_pw(data.app_locale.enter_encryp_pw, &data.app_locale),
 			};
 		}
-		encrypt_via_cli(&file_output,
-							&data.app_line_vec,
+		encrypt_via_cli(file_output,
+							&data.vault_lines(),
 							&data.app_encryp_pass).unwrap_or_else(|e| {
 			eprintln!("{} {}", data.app_locale.err_err, e);
 			std::process::exit(20);
 		});
 	};
+	// Issue d'une commande: sauvegarde si nécessaire et code de sortie
+	let conclude = |outcome: Outcome, data: &mut AppData| -> ExitCode {
+		match outcome {
+			Outcome::Changed => {
+				if let Some(op) = journal_op(&config.command) {
+					let lignes_apres = data.entry_lines();
+					journal::record(&mut data.app_journal, op, date::now(), &lignes_avant, &lignes_apres);
+				}
+				finalize_encryption(data);
+				ExitCode::SUCCESS
+			}
+			Outcome::Unchanged => ExitCode::SUCCESS,
+			Outcome::NotFound => ExitCode::from(EXIT_NOT_FOUND),
+			Outcome::Ambiguous => ExitCode::from(EXIT_AMBIGUOUS),
+			Outcome::Failed => ExitCode::FAILURE,
+		}
+	};
 	// ############################################################################
 	// Exécution de la commande selon l'énumération
 	match &config.command {		// Deuxième tri, ces commandes font affaire avec un fichier => openssl
@@ -187,7 +316,8 @@ pub fn main() -> ExitCode {
 				println!("{}", app_data.app_locale.find_header
 									.replace("{1}", pattern)
 									.replace("{2}", &mdp_full_path));
-				actions::find(&pattern, &app_data);
+				let outcome = actions::find(&query, &mut app_data);
+				return conclude(outcome, &mut app_data);
 			}
 			else {
 				eprintln!("{}", app_data.app_locale.find_no_file.replace("{1}", &mdp_full_path));
@@ -199,37 +329,249 @@ pub fn main() -> ExitCode {
 				println!("{}", app_data.app_locale.del_header
 									.replace("{1}", pattern)
 									.replace("{2}", &mdp_full_path));
-				if actions::del(&pattern, &mut app_data) {
-					encrypt_via_cli(&file_output,
-								&app_data.app_line_vec,
-								&app_data.app_encryp_pass).unwrap_or_else(|e| {
-					eprintln!("{} {}", app_data.app_locale.err_err, e);
-					std::process::exit(20); // Arrêt immédiat si le déchiffrement échoue
-					})
-				}
+				let outcome = actions::del(&query, &mut app_data);
+				return conclude(outcome, &mut app_data);
 			}
 			else {
 				eprintln!("{}", app_data.app_locale.del_no_file.replace("{1}", &mdp_full_path));
 				return ExitCode::FAILURE;
 			}
 		}
-		CommandsOptions::Add(desc, pw) => {
-			app_data.app_line_vec.push(format!("{pw}∫∆∫{desc}"));
-			println!("{}", app_data.app_locale.add_new_save.replace("{1}", pw).replace("{2}", &mdp_full_path));
+		CommandsOptions::Get(_) => {
+			if mdp_file_exists {
+				let field = config.field.as_deref().unwrap_or("password");
+				let outcome = actions::get(&query, field, &mut app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Trash => {
+			if mdp_file_exists {
+				actions::list_trash(&app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Undelete(_) => {
+			if mdp_file_exists {
+				let outcome = actions::undelete(&query, &mut app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::EmptyTrash => {
+			if mdp_file_exists {
+				let outcome = actions::empty_trash(config.older_than, &mut app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Undo(n) => {
+			if mdp_file_exists {
+				let outcome = actions::undo(*n, &mut app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::List(_) => {
+			if mdp_file_exists {
+				let outcome = actions::list(&query, &config.list, config.older_than, &app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Due(jours) => {
+			if mdp_file_exists {
+				let outcome = actions::due(*jours, &app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::SetExpiry(_) => {
+			if mdp_file_exists {
+				let outcome = actions::set_expiry(&query, &mut app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Attach(_, nom) | CommandsOptions::Detach(_, nom) | CommandsOptions::Extract(_, nom) => {
+			if mdp_file_exists {
+				let outcome = match config.command {
+					CommandsOptions::Attach(_, _) => actions::attach(&query, &repertoire_depart.join(nom), &mut app_data),
+					CommandsOptions::Detach(_, _) => actions::detach(&query, nom, &mut app_data),
+					_ => {
+						let destination = config.extract_to.as_ref().map(|chemin| repertoire_depart.join(chemin));
+						actions::extract(&query, nom, destination.as_deref(), &mut app_data)
+					}
+				};
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::SetField(_, nom, valeur) => {
+			if mdp_file_exists {
+				// Un secret passé en argument reste dans l'historique: même avertissement que pour -a
+				if config.secret_field && valeur.is_some() {
+					eprintln!("{}", app_data.app_locale.warn_secret_argv);
+				}
+				let outcome = actions::set_field(&query, nom, valeur.as_deref(), config.secret_field, &mut app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Url(url) => {
+			if mdp_file_exists {
+				println!("{}", app_data.app_locale.url_header
+									.replace("{1}", url)
+									.replace("{2}", &mdp_full_path));
+				let outcome = actions::find_url(url, config.url_match.unwrap_or_default(), &mut app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.find_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Attachments(_) => {
+			if mdp_file_exists {
+				let outcome = actions::list_attachments(&query, &app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Tags | CommandsOptions::Folders => {
+			if mdp_file_exists {
+				if let CommandsOptions::Tags = config.command { actions::list_tags(&app_data); }
+				else { actions::list_folders(&app_data); }
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Log => {
+			if mdp_file_exists {
+				actions::show_log(&app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Otp(_) | CommandsOptions::OtpSet(_) => {
+			if mdp_file_exists {
+				let outcome = match config.command {
+					CommandsOptions::Otp(_) => actions::show_otp(&query, &mut app_data),
+					_ => actions::set_otp(&query, &mut app_data),
+				};
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Qr(_) => {
+			if mdp_file_exists {
+				let outcome = actions::show_qr(&query, config.field.as_deref(), &mut app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::ImportOtp => {
+			let outcome = actions::import_otp(&mut app_data);
+			return conclude(outcome, &mut app_data);
+		}
+		CommandsOptions::Dedupe => {
+			if mdp_file_exists {
+				let outcome = actions::dedupe(&mut app_data);
+				return conclude(outcome, &mut app_data);
+			}
+			else {
+				eprintln!("{}", app_data.app_locale.get_no_file.replace("{1}", &mdp_full_path));
+				return ExitCode::FAILURE;
+			}
+		}
+		CommandsOptions::Add(desc, pw_arg) => {
+			let modele = template::find(config.entry_type.as_deref().unwrap_or(template::LOGIN))
+							.unwrap_or(&template::TEMPLATES[0]);
+			let pw = match pw_arg {
+				Some(pw) => {
+					eprintln!("{}", app_data.app_locale.warn_pw_argv);
+					pw.clone()
+				}
+				None => match actions::read_new_pw(config.password_stdin, modele.name != template::LOGIN, &app_data) {
+					Some(pw) => pw,
+					None => return ExitCode::FAILURE,
+				},
+			};
+			let mut nouvelle = template::new_entry(desc, &pw, modele);
+			if let Some(url) = &config.entry_url { nouvelle.set(entry::URL, url); }
+			let Some(titre) = actions::add_entry(nouvelle, &mut app_data) else {
+				return ExitCode::FAILURE;
+			};
+			println!("{}", app_data.app_locale.add_new_save.replace("{1}", &titre).replace("{2}", &mdp_full_path));
+
+			return conclude(Outcome::Changed, &mut app_data);
+		}
+		CommandsOptions::Wizard => {
+			let Some(titre) = actions::wizard(config.entry_type.as_deref(), &mut app_data) else {
+				return ExitCode::FAILURE;
+			};
+			println!("{}", app_data.app_locale.add_new_save.replace("{1}", &titre).replace("{2}", &mdp_full_path));
 
-			finalize_encryption(&mut app_data);
+			return conclude(Outcome::Changed, &mut app_data);
 		}
 		CommandsOptions::New(desc) => {
 			let new_pw = generator::gen_pass(app_pw_len);
-			app_data.app_line_vec.push(format!("{new_pw}∫∆∫{desc}"));
-			println!("{}", app_data.app_locale.add_new_save.replace("{1}", desc).replace("{2}", &mdp_full_path));
+			let mut nouvelle = Entry::new(desc, &new_pw);
+			if let Some(url) = &config.entry_url { nouvelle.set(entry::URL, url); }
+			let Some(titre) = actions::add_entry(nouvelle, &mut app_data) else {
+				return ExitCode::FAILURE;
+			};
+			println!("{}", app_data.app_locale.add_new_save.replace("{1}", &titre).replace("{2}", &mdp_full_path));
 			if command_exist("pbcopy", &app_data.app_locale) {
 				send_to_clipboard(&new_pw);
 				println!("{} {new_pw} ==> Clipboard",app_data.app_locale.mdp_gen_str);
 			}
 			else { println!("{} {new_pw}", app_data.app_locale.mdp_gen_str); }
 
-			finalize_encryption(&mut app_data);
+			return conclude(Outcome::Changed, &mut app_data);
 		}
 		CommandsOptions::None => {
 			eprintln!("🛑 {}", app_data.app_locale.err_interne);
@@ -25,12 +25,12 @@ pub fn decrypt_via_cli(encrypted_filepath: &str, password: &str) -> Result<Vec<S
 		let lines = content.lines().map(|s| s.to_string()).collect();
 		Ok(lines)
 	} else {
-		Err(Error::new(ErrorKind::Other, "Échec du déchiffrement OpenSSL"))
+		Err(Error::other("Échec du déchiffrement OpenSSL"))
 	}
 }
 
 /// Chiffre un Vec<String> et sauvegarde le résultat dans un fichier.
-pub fn encrypt_via_cli(output_file: &str, data: &Vec<String>, password: &str) -> Result<(), Error> {
+pub fn encrypt_via_cli(output_file: &str, data: &[String], password: &str) -> Result<(), Error> {
 	// Préparation des données : on joint les lignes avec un saut de ligne
 	let input_text = data.join("\n");
 
@@ -55,6 +55,6 @@ pub fn encrypt_via_cli(output_file: &str, data: &Vec<String>, password: &str) ->
 	if status.success() {
 		Ok(())
 	} else {
-		Err(Error::new(ErrorKind::Other, "Échec du chiffrement OpenSSL"))
+		Err(Error::other("Échec du chiffrement OpenSSL"))
 	}
 }
\ No newline at end of file
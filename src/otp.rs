@@ -0,0 +1,592 @@
+// otp.rs
+
+// Codes à usage unique (2FA), calculés hors ligne:
+//     HOTP (RFC 4226): HMAC(secret, compteur) tronqué à N chiffres
+//     TOTP (RFC 6238): HOTP avec compteur = temps UNIX / période
+// Le secret est une URI 'otpauth://totp/...?secret=...' (ou hotp) ou un secret base32 seul (TOTP, 6 chiffres, 30 s).
+// SHA-1, SHA-256 et SHA-512 sont codés ici: aucune dépendance.
+// L'importation lit aussi les exports de Google Authenticator (otpauth-migration://, protobuf).
+
+use crate::base64;
+
+// --- 1. Structures de Données ---
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Algorithm {
+	Sha1,
+	Sha256,
+	Sha512,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+	Totp { period: u64 },
+	Hotp { counter: u64 },
+}
+
+#[derive(Debug, Clone)]
+pub struct Otp {
+	pub kind: Kind,
+	pub secret: Vec<u8>,
+	pub digits: u32,
+	pub algorithm: Algorithm,
+}
+
+// --- 2. Analyse du secret ---
+
+/// Décode du base32 (RFC 4648), sans égard à la casse, aux espaces ni au remplissage '='.
+pub fn base32_decode(texte: &str) -> Option<Vec<u8>> {
+	const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+	let mut octets = Vec::new();
+	let mut tampon: u64 = 0;
+	let mut bits = 0;
+	for c in texte.chars().filter(|c| !c.is_whitespace() && *c != '=' && *c != '-') {
+		let valeur = ALPHABET.iter().position(|&a| a as char == c.to_ascii_uppercase())?;
+		tampon = (tampon << 5) | valeur as u64;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			octets.push((tampon >> bits) as u8);
+		}
+	}
+	Some(octets)
+}
+
+/// Encode en base32 (RFC 4648), sans remplissage.
+pub fn base32_encode(octets: &[u8]) -> String {
+	const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+	let mut texte = String::new();
+	let mut tampon: u64 = 0;
+	let mut bits = 0;
+	for &o in octets {
+		tampon = (tampon << 8) | o as u64;
+		bits += 8;
+		while bits >= 5 {
+			bits -= 5;
+			texte.push(ALPHABET[((tampon >> bits) & 31) as usize] as char);
+		}
+	}
+	if bits > 0 {
+		texte.push(ALPHABET[((tampon << (5 - bits)) & 31) as usize] as char);
+	}
+	texte
+}
+
+/// Encode pour une URI: tout sauf les lettres, chiffres et '-._~' devient '%XX'.
+pub fn percent_encode(texte: &str) -> String {
+	texte.bytes()
+		.map(|o| if o.is_ascii_alphanumeric() || b"-._~".contains(&o) { (o as char).to_string() } else { format!("%{o:02X}") })
+		.collect()
+}
+
+/// Décode les séquences '%XX' d'une URI ('+' reste tel quel).
+pub fn percent_decode(texte: &str) -> String {
+	let octets = texte.as_bytes();
+	let mut resultat = Vec::with_capacity(octets.len());
+	let mut i = 0;
+	while i < octets.len() {
+		let hex = octets.get(i + 1..i + 3)
+			.and_then(|h| std::str::from_utf8(h).ok())
+			.and_then(|h| u8::from_str_radix(h, 16).ok());
+		match (octets[i], hex) {
+			(b'%', Some(o)) => { resultat.push(o); i += 3; }
+			(o, _) => { resultat.push(o); i += 1; }
+		}
+	}
+	String::from_utf8_lossy(&resultat).into_owned()
+}
+
+/// Paramètres 'clé=valeur' d'une URI, décodés.
+fn query_params(uri: &str) -> Vec<(String, String)> {
+	let Some((_, requete)) = uri.split_once('?') else { return Vec::new() };
+	requete.split('&')
+		.filter_map(|p| p.split_once('='))
+		.map(|(c, v)| (c.to_ascii_lowercase(), percent_decode(v)))
+		.collect()
+}
+
+impl Otp {
+	/// Lit une URI otpauth:// ou un secret base32. None si le secret est illisible.
+	pub fn parse(texte: &str) -> Option<Self> {
+		let texte = texte.trim();
+		let Some(reste) = texte.strip_prefix("otpauth://") else {
+			// Un secret seul d'au moins 80 bits (16 caractères): évite de prendre un mot pour un secret
+			let secret = base32_decode(texte).filter(|s| s.len() >= 10)?;
+			return Some(Self { kind: Kind::Totp { period: 30 }, secret, digits: 6, algorithm: Algorithm::Sha1 });
+		};
+
+		let params = query_params(reste);
+		let param = |nom: &str| params.iter().find(|(c, _)| c == nom).map(|(_, v)| v.as_str());
+		let secret = base32_decode(param("secret")?).filter(|s| !s.is_empty())?;
+		let digits = match param("digits") {
+			Some(d) => d.parse().ok().filter(|d| (6..=10).contains(d))?,
+			None => 6,
+		};
+		let algorithm = match param("algorithm").map(str::to_ascii_uppercase).as_deref() {
+			None | Some("SHA1") => Algorithm::Sha1,
+			Some("SHA256") => Algorithm::Sha256,
+			Some("SHA512") => Algorithm::Sha512,
+			Some(_) => return None,
+		};
+		let kind = match reste.split('/').next().map(str::to_ascii_lowercase).as_deref() {
+			Some("totp") => Kind::Totp { period: param("period").map_or(Some(30), |p| p.parse().ok().filter(|&p| p > 0))? },
+			Some("hotp") => Kind::Hotp { counter: param("counter").map_or(Some(0), |c| c.parse().ok())? },
+			_ => return None,
+		};
+		Some(Self { kind, secret, digits, algorithm })
+	}
+
+	/// Code courant et, pour TOTP, les secondes qui restent avant le suivant.
+	pub fn code(&self, maintenant: u64) -> (String, Option<u64>) {
+		match self.kind {
+			Kind::Totp { period } => {
+				let code = hotp(self.algorithm, &self.secret, maintenant / period, self.digits);
+				(code, Some(period - maintenant % period))
+			}
+			Kind::Hotp { counter } => (hotp(self.algorithm, &self.secret, counter, self.digits), None),
+		}
+	}
+}
+
+/// Remplace (ou ajoute) le compteur d'une URI hotp, après usage d'un code.
+pub fn with_counter(uri: &str, counter: u64) -> String {
+	let (base, requete) = uri.split_once('?').unwrap_or((uri, ""));
+	let mut params: Vec<String> = requete.split('&')
+		.filter(|p| !p.is_empty() && !p.to_ascii_lowercase().starts_with("counter="))
+		.map(str::to_string)
+		.collect();
+	params.push(format!("counter={counter}"));
+	format!("{base}?{}", params.join("&"))
+}
+
+// --- 3. Importation ---
+
+/// Compte 2FA importé: émetteur (service), compte et URI otpauth:// à enregistrer.
+#[derive(Debug)]
+pub struct Imported {
+	pub issuer: String,
+	pub account: String,
+	pub uri: String,
+}
+
+/// Décode une ligne à importer: URI otpauth:// (un compte) ou otpauth-migration:// (plusieurs).
+/// None si la ligne est illisible.
+pub fn parse_import(ligne: &str) -> Option<Vec<Imported>> {
+	let ligne = ligne.trim();
+	if let Some(reste) = ligne.strip_prefix("otpauth-migration://") {
+		let data = query_params(reste).into_iter().find(|(c, _)| c == "data")?.1;
+		// 'data' est du base64 encodé pour l'URI ('%2B' pour '+', '%2F' pour '/')
+		return migration(&base64::decode(&data)?);
+	}
+	let reste = ligne.strip_prefix("otpauth://")?;
+	Otp::parse(ligne)?;
+
+	// Libellé: 'otpauth://totp/Émetteur:compte?...'; le paramètre 'issuer' a priorité
+	let chemin = reste.split('?').next().unwrap_or_default();
+	let libelle = percent_decode(chemin.split_once('/').map_or("", |(_, l)| l));
+	let (prefixe, account) = match libelle.split_once(':') {
+		Some((e, c)) => (e.trim().to_string(), c.trim().to_string()),
+		None => (String::new(), libelle.trim().to_string()),
+	};
+	let issuer = query_params(reste).into_iter()
+		.find(|(c, _)| c == "issuer")
+		.map_or(prefixe, |(_, v)| v);
+	Some(vec![Imported { issuer, account, uri: ligne.to_string() }])
+}
+
+/// Lecteur protobuf minimal: (numéro de champ, valeur entière ou octets).
+enum Valeur<'a> {
+	Entier(u64),
+	Octets(&'a [u8]),
+}
+
+fn varint(donnees: &[u8], pos: &mut usize) -> Option<u64> {
+	let mut valeur = 0u64;
+	for decalage in (0..64).step_by(7) {
+		let octet = *donnees.get(*pos)?;
+		*pos += 1;
+		valeur |= ((octet & 0x7f) as u64) << decalage;
+		if octet & 0x80 == 0 { return Some(valeur); }
+	}
+	None
+}
+
+fn champs_protobuf(donnees: &[u8]) -> Option<Vec<(u64, Valeur<'_>)>> {
+	let mut champs = Vec::new();
+	let mut pos = 0;
+	while pos < donnees.len() {
+		let cle = varint(donnees, &mut pos)?;
+		let valeur = match cle & 7 {
+			0 => Valeur::Entier(varint(donnees, &mut pos)?),
+			2 => {
+				let longueur = varint(donnees, &mut pos)? as usize;
+				let octets = donnees.get(pos..pos.checked_add(longueur)?)?;
+				pos += longueur;
+				Valeur::Octets(octets)
+			}
+			// Champs fixes de 64 et 32 bits: ignorés
+			1 => { pos += 8; continue; }
+			5 => { pos += 4; continue; }
+			_ => return None,
+		};
+		champs.push((cle >> 3, valeur));
+	}
+	Some(champs)
+}
+
+// MigrationPayload { repeated OtpParameters otp_parameters = 1; ... }
+// OtpParameters { bytes secret = 1; string name = 2; string issuer = 3; Algorithm algorithm = 4;
+//                 DigitCount digits = 5; OtpType type = 6; int64 counter = 7; }
+fn migration(donnees: &[u8]) -> Option<Vec<Imported>> {
+	let mut comptes = Vec::new();
+	for (numero, valeur) in champs_protobuf(donnees)? {
+		let (1, Valeur::Octets(parametres)) = (numero, valeur) else { continue };
+		let mut secret: &[u8] = &[];
+		let (mut nom, mut issuer) = (String::new(), String::new());
+		let (mut algorithme, mut chiffres, mut hotp, mut compteur) = ("SHA1", 6, false, 0);
+		for (n, v) in champs_protobuf(parametres)? {
+			match (n, v) {
+				(1, Valeur::Octets(o)) => secret = o,
+				(2, Valeur::Octets(o)) => nom = String::from_utf8_lossy(o).into_owned(),
+				(3, Valeur::Octets(o)) => issuer = String::from_utf8_lossy(o).into_owned(),
+				(4, Valeur::Entier(a)) => algorithme = match a { 2 => "SHA256", 3 => "SHA512", 4 => return None, _ => "SHA1" },
+				(5, Valeur::Entier(d)) => chiffres = if d == 2 { 8 } else { 6 },
+				(6, Valeur::Entier(t)) => hotp = t == 1,
+				(7, Valeur::Entier(c)) => compteur = c,
+				_ => { }
+			}
+		}
+		if secret.is_empty() { return None; }
+
+		// Le nom peut déjà contenir 'Émetteur:compte'
+		let account = match nom.split_once(':') {
+			Some((e, c)) if issuer.is_empty() || e.trim() == issuer => c.trim().to_string(),
+			_ => nom.trim().to_string(),
+		};
+		let libelle = if issuer.is_empty() { percent_encode(&account) }
+			else { format!("{}:{}", percent_encode(&issuer), percent_encode(&account)) };
+		let mut uri = format!("otpauth://{}/{libelle}?secret={}", if hotp { "hotp" } else { "totp" }, base32_encode(secret));
+		if !issuer.is_empty() { uri.push_str(&format!("&issuer={}", percent_encode(&issuer))); }
+		if algorithme != "SHA1" { uri.push_str(&format!("&algorithm={algorithme}")); }
+		if chiffres != 6 { uri.push_str(&format!("&digits={chiffres}")); }
+		if hotp { uri.push_str(&format!("&counter={compteur}")); }
+		comptes.push(Imported { issuer, account, uri });
+	}
+	Some(comptes)
+}
+
+// --- 4. Calcul du code ---
+
+/// Code HOTP (RFC 4226): troncature dynamique du HMAC du compteur.
+pub fn hotp(algorithm: Algorithm, secret: &[u8], counter: u64, digits: u32) -> String {
+	let mac = hmac(algorithm, secret, &counter.to_be_bytes());
+	let decalage = (mac[mac.len() - 1] & 0x0f) as usize;
+	let valeur = u32::from_be_bytes([mac[decalage] & 0x7f, mac[decalage + 1], mac[decalage + 2], mac[decalage + 3]]);
+	let code = valeur as u64 % 10u64.pow(digits);
+	format!("{code:0width$}", width = digits as usize)
+}
+
+type Hash = fn(&[u8]) -> Vec<u8>;
+
+fn hmac(algorithm: Algorithm, cle: &[u8], message: &[u8]) -> Vec<u8> {
+	let (hash, bloc): (Hash, usize) = match algorithm {
+		Algorithm::Sha1 => (sha1, 64),
+		Algorithm::Sha256 => (sha256, 64),
+		Algorithm::Sha512 => (sha512, 128),
+	};
+	let mut k = if cle.len() > bloc { hash(cle) } else { cle.to_vec() };
+	k.resize(bloc, 0);
+
+	let mut interne: Vec<u8> = k.iter().map(|b| b ^ 0x36).collect();
+	interne.extend_from_slice(message);
+	let mut externe: Vec<u8> = k.iter().map(|b| b ^ 0x5c).collect();
+	externe.extend(hash(&interne));
+	hash(&externe)
+}
+
+// Remplissage commun: 0x80, des zéros, puis la longueur en bits (sur 'taille_longueur' octets).
+fn pad(message: &[u8], bloc: usize, taille_longueur: usize) -> Vec<u8> {
+	let mut donnees = message.to_vec();
+	donnees.push(0x80);
+	while donnees.len() % bloc != bloc - taille_longueur {
+		donnees.push(0);
+	}
+	let bits = (message.len() as u128) * 8;
+	donnees.extend_from_slice(&bits.to_be_bytes()[16 - taille_longueur..]);
+	donnees
+}
+
+fn sha1(message: &[u8]) -> Vec<u8> {
+	let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+	for bloc in pad(message, 64, 8).chunks(64) {
+		let mut w = [0u32; 80];
+		for t in 0..16 {
+			w[t] = u32::from_be_bytes([bloc[4 * t], bloc[4 * t + 1], bloc[4 * t + 2], bloc[4 * t + 3]]);
+		}
+		for t in 16..80 {
+			w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(1);
+		}
+		let [mut a, mut b, mut c, mut d, mut e] = h;
+		for (t, &mot) in w.iter().enumerate() {
+			let (f, k) = match t {
+				0..=19 => ((b & c) | (!b & d), 0x5A827999),
+				20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+				40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+				_ => (b ^ c ^ d, 0xCA62C1D6),
+			};
+			let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(mot);
+			e = d;
+			d = c;
+			c = b.rotate_left(30);
+			b = a;
+			a = temp;
+		}
+		for (x, y) in h.iter_mut().zip([a, b, c, d, e]) {
+			*x = x.wrapping_add(y);
+		}
+	}
+	h.iter().flat_map(|x| x.to_be_bytes()).collect()
+}
+
+const K256: [u32; 64] = [
+	0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+	0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+	0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+	0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+	0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+	0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+	0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+	0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+pub fn sha256(message: &[u8]) -> Vec<u8> {
+	let mut h: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+	for bloc in pad(message, 64, 8).chunks(64) {
+		let mut w = [0u32; 64];
+		for t in 0..16 {
+			w[t] = u32::from_be_bytes([bloc[4 * t], bloc[4 * t + 1], bloc[4 * t + 2], bloc[4 * t + 3]]);
+		}
+		for t in 16..64 {
+			let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+			let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+			w[t] = w[t - 16].wrapping_add(s0).wrapping_add(w[t - 7]).wrapping_add(s1);
+		}
+		let mut v = h;
+		for t in 0..64 {
+			let [a, b, c, d, e, f, g, hh] = v;
+			let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+			let ch = (e & f) ^ (!e & g);
+			let t1 = hh.wrapping_add(s1).wrapping_add(ch).wrapping_add(K256[t]).wrapping_add(w[t]);
+			let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+			let maj = (a & b) ^ (a & c) ^ (b & c);
+			let t2 = s0.wrapping_add(maj);
+			v = [t1.wrapping_add(t2), a, b, c, d.wrapping_add(t1), e, f, g];
+		}
+		for (x, y) in h.iter_mut().zip(v) {
+			*x = x.wrapping_add(y);
+		}
+	}
+	h.iter().flat_map(|x| x.to_be_bytes()).collect()
+}
+
+const K512: [u64; 80] = [
+	0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc, 0x3956c25bf348b538,
+	0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118, 0xd807aa98a3030242, 0x12835b0145706fbe,
+	0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2, 0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235,
+	0xc19bf174cf692694, 0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+	0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5, 0x983e5152ee66dfab,
+	0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4, 0xc6e00bf33da88fc2, 0xd5a79147930aa725,
+	0x06ca6351e003826f, 0x142929670a0e6e70, 0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed,
+	0x53380d139d95b3df, 0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+	0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30, 0xd192e819d6ef5218,
+	0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8, 0x19a4c116b8d2d0c8, 0x1e376c085141ab53,
+	0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8, 0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373,
+	0x682e6ff3d6b2b8a3, 0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+	0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b, 0xca273eceea26619c,
+	0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178, 0x06f067aa72176fba, 0x0a637dc5a2c898a6,
+	0x113f9804bef90dae, 0x1b710b35131c471b, 0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc,
+	0x431d67c49c100d4c, 0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+];
+
+fn sha512(message: &[u8]) -> Vec<u8> {
+	let mut h: [u64; 8] = [
+		0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+		0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+	];
+	for bloc in pad(message, 128, 16).chunks(128) {
+		let mut w = [0u64; 80];
+		for t in 0..16 {
+			let mut mot = [0u8; 8];
+			mot.copy_from_slice(&bloc[8 * t..8 * t + 8]);
+			w[t] = u64::from_be_bytes(mot);
+		}
+		for t in 16..80 {
+			let s0 = w[t - 15].rotate_right(1) ^ w[t - 15].rotate_right(8) ^ (w[t - 15] >> 7);
+			let s1 = w[t - 2].rotate_right(19) ^ w[t - 2].rotate_right(61) ^ (w[t - 2] >> 6);
+			w[t] = w[t - 16].wrapping_add(s0).wrapping_add(w[t - 7]).wrapping_add(s1);
+		}
+		let mut v = h;
+		for t in 0..80 {
+			let [a, b, c, d, e, f, g, hh] = v;
+			let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+			let ch = (e & f) ^ (!e & g);
+			let t1 = hh.wrapping_add(s1).wrapping_add(ch).wrapping_add(K512[t]).wrapping_add(w[t]);
+			let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+			let maj = (a & b) ^ (a & c) ^ (b & c);
+			let t2 = s0.wrapping_add(maj);
+			v = [t1.wrapping_add(t2), a, b, c, d.wrapping_add(t1), e, f, g];
+		}
+		for (x, y) in h.iter_mut().zip(v) {
+			*x = x.wrapping_add(y);
+		}
+	}
+	h.iter().flat_map(|x| x.to_be_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn hex(octets: &[u8]) -> String {
+		octets.iter().map(|o| format!("{o:02x}")).collect()
+	}
+
+	#[test]
+	fn empreintes_fips_180() {
+		assert_eq!(hex(&sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+		assert_eq!(hex(&sha1(b"abc")), "a9993e364706816aba3e25717850c26c9cd0d89d");
+		assert_eq!(hex(&sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+		// Deux blocs: le remplissage déborde du premier
+		assert_eq!(hex(&sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+				   "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+		assert_eq!(hex(&sha512(b"abc")),
+				   "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+					2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f");
+	}
+
+	#[test]
+	fn hmac_rfc_2202_et_4231() {
+		let (cle, message) = (b"Jefe", b"what do ya want for nothing?");
+		assert_eq!(hex(&hmac(Algorithm::Sha1, cle, message)), "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79");
+		assert_eq!(hex(&hmac(Algorithm::Sha256, cle, message)),
+				   "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+		assert_eq!(hex(&hmac(Algorithm::Sha512, cle, message)),
+				   "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+					9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737");
+		// Clé plus longue qu'un bloc: elle est d'abord hachée (RFC 4231, cas 6)
+		assert_eq!(hex(&hmac(Algorithm::Sha256, &[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First")),
+				   "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54");
+	}
+
+	#[test]
+	fn hotp_rfc_4226() {
+		let attendus = ["755224", "287082", "359152", "969429", "338314",
+						"254676", "287922", "162583", "399871", "520489"];
+		for (compteur, attendu) in attendus.iter().enumerate() {
+			assert_eq!(hotp(Algorithm::Sha1, b"12345678901234567890", compteur as u64, 6), *attendu);
+		}
+	}
+
+	#[test]
+	fn totp_rfc_6238() {
+		let graines: [(Algorithm, &[u8]); 3] = [
+			(Algorithm::Sha1, b"12345678901234567890"),
+			(Algorithm::Sha256, b"12345678901234567890123456789012"),
+			(Algorithm::Sha512, b"1234567890123456789012345678901234567890123456789012345678901234"),
+		];
+		let vecteurs: [(u64, [&str; 3]); 6] = [
+			(59, ["94287082", "46119246", "90693936"]),
+			(1111111109, ["07081804", "68084774", "25091201"]),
+			(1111111111, ["14050471", "67062674", "99943326"]),
+			(1234567890, ["89005924", "91819424", "93441116"]),
+			(2000000000, ["69279037", "90698825", "38618901"]),
+			(20000000000, ["65353130", "77737706", "47863826"]),
+		];
+		for (temps, codes) in vecteurs {
+			for ((algorithm, secret), attendu) in graines.iter().zip(codes) {
+				let otp = Otp { kind: Kind::Totp { period: 30 }, secret: secret.to_vec(), digits: 8, algorithm: *algorithm };
+				assert_eq!(otp.code(temps).0, attendu, "{algorithm:?} à {temps}");
+			}
+		}
+		let otp = Otp { kind: Kind::Totp { period: 30 }, secret: b"12345678901234567890".to_vec(), digits: 8, algorithm: Algorithm::Sha1 };
+		assert_eq!(otp.code(59).1, Some(1));
+	}
+
+	#[test]
+	fn base32_rfc_4648() {
+		let vecteurs = [("", ""), ("f", "MY"), ("fo", "MZXQ"), ("foo", "MZXW6"), ("foob", "MZXW6YQ"),
+						("fooba", "MZXW6YTB"), ("foobar", "MZXW6YTBOI")];
+		for (clair, code) in vecteurs {
+			assert_eq!(base32_encode(clair.as_bytes()), code);
+			assert_eq!(base32_decode(code).as_deref(), Some(clair.as_bytes()));
+		}
+		// Casse, blancs, tirets et remplissage ignorés; caractère hors alphabet refusé
+		assert_eq!(base32_decode("mzxw 6ytb-oi======").as_deref(), Some(&b"foobar"[..]));
+		assert_eq!(base32_decode("MZXW1"), None);
+		let octets: Vec<u8> = (0..=255).collect();
+		assert_eq!(base32_decode(&base32_encode(&octets)), Some(octets));
+	}
+
+	// Paramètres d'un compte d'export Google Authenticator, encodés à la main
+	fn parametres(champs: &[(u8, &[u8])], entiers: &[(u8, u8)]) -> Vec<u8> {
+		let mut octets = Vec::new();
+		for (n, valeur) in champs {
+			octets.extend([n << 3 | 2, valeur.len() as u8]);
+			octets.extend_from_slice(valeur);
+		}
+		for (n, valeur) in entiers { octets.extend([n << 3, *valeur]); }
+		octets
+	}
+
+	#[test]
+	fn export_google_authenticator() {
+		let totp = parametres(&[(1, b"12345678901234567890"), (2, b"Bob:bob@x.com"), (3, b"Bob")], &[(4, 1), (5, 1), (6, 2)]);
+		let hotp = parametres(&[(1, b"12345678901234567890"), (2, b"carte")], &[(4, 2), (5, 2), (6, 1), (7, 5)]);
+		let mut export = Vec::new();
+		for compte in [&totp, &hotp] {
+			export.extend([1 << 3 | 2, compte.len() as u8]);
+			export.extend_from_slice(compte);
+		}
+		export.extend([2 << 3, 1]);		// version: champ entier ignoré
+		let ligne = format!("otpauth-migration://offline?data={}", percent_encode(&base64::encode(&export)));
+
+		let comptes = parse_import(&ligne).expect("export valide");
+		assert_eq!(comptes.len(), 2);
+		assert_eq!((comptes[0].issuer.as_str(), comptes[0].account.as_str()), ("Bob", "bob@x.com"));
+		assert_eq!(comptes[0].uri, "otpauth://totp/Bob:bob%40x.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&issuer=Bob");
+		assert_eq!(comptes[1].uri, "otpauth://hotp/carte?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&algorithm=SHA256&digits=8&counter=5");
+		assert!(comptes.iter().all(|c| Otp::parse(&c.uri).is_some()));
+
+		// Export tronqué ou algorithme MD5: refusé plutôt qu'importé de travers
+		let tronque = format!("otpauth-migration://offline?data={}", percent_encode(&base64::encode(&export[..export.len() - 8])));
+		assert!(parse_import(&tronque).is_none());
+		let md5 = parametres(&[(1, b"12345678901234567890")], &[(4, 4)]);
+		let mut export = vec![1 << 3 | 2, md5.len() as u8];
+		export.extend_from_slice(&md5);
+		assert!(parse_import(&format!("otpauth-migration://offline?data={}", base64::encode(&export))).is_none());
+	}
+
+	#[test]
+	fn import_d_une_uri() {
+		let comptes = parse_import("otpauth://totp/Ancien%20nom:alice?secret=GEZDGNBVGY3TQOJQ&issuer=ACME").expect("URI valide");
+		assert_eq!((comptes[0].issuer.as_str(), comptes[0].account.as_str()), ("ACME", "alice"));
+		assert!(parse_import("otpauth://totp/x?secret=!!").is_none());
+		assert!(parse_import("https://exemple.com").is_none());
+	}
+
+	#[test]
+	fn lecture_des_uri() {
+		let otp = Otp::parse("otpauth://hotp/X:bob?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&digits=8&counter=7&algorithm=sha256")
+			.expect("URI valide");
+		assert_eq!(otp.kind, Kind::Hotp { counter: 7 });
+		assert_eq!((otp.digits, otp.algorithm), (8, Algorithm::Sha256));
+		assert_eq!(otp.secret, b"12345678901234567890");
+		assert!(Otp::parse("otpauth://totp/X?secret=GEZDGNBV&digits=5").is_none());
+		assert!(Otp::parse("otpauth://totp/X?secret=GEZDGNBV&algorithm=MD5").is_none());
+		// Un mot court n'est pas pris pour un secret base32
+		assert!(Otp::parse("gmail").is_none());
+
+		let uri = with_counter("otpauth://hotp/X?secret=GEZDGNBV&counter=7&digits=6", 8);
+		assert_eq!(uri, "otpauth://hotp/X?secret=GEZDGNBV&digits=6&counter=8");
+	}
+}
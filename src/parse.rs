@@ -1,8 +1,14 @@
 // parse.rs
 
 use std::env;
+use std::path::Path;
 
+use crate::date;
+use crate::domain;
+use crate::entry;
 use crate::locale::LangStrings;
+use crate::search;
+use crate::template;
 
 // --- 1. Structures de Données ---
 
@@ -13,18 +19,96 @@ pub enum CommandsOptions {
 	Version(String),
 	Find(String),
 	Delete(String),
+	Get(String),
+	Trash,
+	Undelete(String),
+	EmptyTrash,
+	Undo(usize),
+	Log,
+	Dedupe,
+	Otp(String),
+	OtpSet(String),
+	ImportOtp,
+	Qr(String),
+	Tags,
+	Folders,
+	List(String),	// Motif optionnel, vide: toutes les entrées
+	Due(u64),		// Horizon en jours
+	SetExpiry(String),
+	Attach(String, String),		// Motif, fichier à joindre
+	Detach(String, String),		// Motif, nom de la pièce jointe
+	Attachments(String),		// Motif optionnel
+	Extract(String, String),	// Motif, nom de la pièce jointe
+	Url(String),
+	SetField(String, String, Option<String>),	// Motif, nom du champ, valeur (sinon saisie)
 	New(String),
-	Add(String, String), // Description, Mot de passe
+	Add(String, Option<String>), // Description, Mot de passe (ancienne forme)
+	Wizard,		// -a sans description: saisie champ par champ
 	GeneratePassword, 
 	None,
 }
 
+// Choix d'une entrée sans interaction (scripts)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selector {
+	Interactive,
+	Index(usize),
+	First,
+	Exact(String),
+}
+
+// Champs que -g/--get peut afficher
+pub const GET_FIELDS: [&str; 5] = ["password", "user", "url", "notes", "otp"];
+
+// Colonnes et tris de --list (le titre est toujours affiché)
+pub const LIST_COLUMNS: [&str; 9] = ["user", "url", "tags", "folder", "type", "created", "modified", "used", "due"];
+pub const LIST_SORTS: [&str; 7] = ["title", "folder", "type", "created", "modified", "used", "due"];
+
+// Options qui peuvent suivre '-a <description>' sans être prises pour le mot de passe
+const ADD_OPTIONS: [&str; 14] = ["-l", "--long", "-s", "--show", "-y", "--yes", "--update", "--type",
+								 "--password-stdin", "--tag", "--folder", "--url", "--expires", "--max-age"];
+
+// Horizon par défaut de --due, en jours
+const DUE_DAYS: u64 = 30;
+
+// Échéance donnée par --expires et --max-age
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Expiry {
+	pub expires: Option<u64>,	// Secondes UNIX
+	pub max_age: Option<u64>,	// Jours
+}
+
+// Présentation de --list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListOptions {
+	pub columns: Vec<String>,
+	pub sort: Option<String>,
+	pub reverse: bool,
+	pub tree: bool,		// Arbre des dossiers plutôt que tableau
+}
+
 // Structure pour stocker la configuration
 #[derive(Debug)]
 pub struct Config {
 	pub command: CommandsOptions,
 	pub password_length: Option<usize>,
 	pub output_file: Option<String>,
+	pub show_passwords: bool,
+	pub search_options: search::Options,
+	pub selector: Selector,
+	pub field: Option<String>,
+	pub assume_yes: bool,
+	pub update: bool,		// --update: -a/-n remplacent l'entrée de même titre sans demander
+	pub older_than: Option<u64>,
+	pub password_stdin: bool,
+	pub entry_type: Option<String>,
+	pub filters: search::Filters,	// Avec -a/-n: étiquettes et dossier de la nouvelle entrée
+	pub entry_url: Option<String>,	// --url avec -a/-n: URL de la nouvelle entrée
+	pub list: ListOptions,
+	pub expiry: Expiry,
+	pub extract_to: Option<String>,		// --to: fichier où extraire la pièce jointe (sinon la sortie standard)
+	pub url_match: Option<domain::Match>,
+	pub secret_field: bool,		// --secret: le champ de --set-field est masqué
 }
 
 // Helper pour déterminer si une commande a déjà été détectée
@@ -32,6 +116,18 @@ impl CommandsOptions {
 	fn is_some(&self) -> bool {
 		!matches!(self, CommandsOptions::None)
 	}
+
+	/// Motif de recherche des commandes qui cherchent des entrées.
+	pub fn pattern(&self) -> Option<&str> {
+		match self {
+			CommandsOptions::Find(p) | CommandsOptions::Delete(p) | CommandsOptions::Get(p)
+			| CommandsOptions::Undelete(p) | CommandsOptions::Otp(p) | CommandsOptions::OtpSet(p)
+			| CommandsOptions::Qr(p) | CommandsOptions::List(p) | CommandsOptions::SetExpiry(p)
+			| CommandsOptions::Attach(p, _) | CommandsOptions::Detach(p, _) | CommandsOptions::Attachments(p)
+			| CommandsOptions::Extract(p, _) | CommandsOptions::SetField(p, _, _) => Some(p),
+			_ => None,
+		}
+	}
 }
 
 // --- 2. Logique de Parsing ---
@@ -46,30 +142,78 @@ pub fn parse_args(mdp_locale: &LangStrings) -> Result<Config, String> {
 			command: CommandsOptions::GeneratePassword,
 			password_length: None,
 			output_file: None,
+			show_passwords: false,
+			search_options: search::Options::default(),
+			selector: Selector::Interactive,
+			field: None,
+			assume_yes: false,
+			update: false,
+			older_than: None,
+			password_stdin: false,
+			entry_type: None,
+			filters: search::Filters::default(),
+			entry_url: None,
+			list: ListOptions::default(),
+			expiry: Expiry::default(),
+			extract_to: None,
+			url_match: None,
+			secret_field: false,
 		});
 	}
 
 	// Règle 1: On passe toute la ligne pour help ou version
 	let mut command: CommandsOptions = CommandsOptions::None;
 
-	for i in 1..args.len() {
-		match args[i].as_str() {
-			"-h" => { command = CommandsOptions::Help(mdp_locale.usage.to_string()); },
-			"--help" => { command = CommandsOptions::Help(mdp_locale.options.to_string()); },
-			"-ver" => { command = CommandsOptions::Version(mdp_locale.ver.to_string()); },
-			"--version" => { command = CommandsOptions::Version(mdp_locale.ver_desc.to_string()); },
-			_ => break
-		}
+	match args[1].as_str() {
+		"-h" => { command = CommandsOptions::Help(mdp_locale.usage.to_string()); },
+		"--help" => { command = CommandsOptions::Help(mdp_locale.options.to_string()); },
+		"-ver" => { command = CommandsOptions::Version(mdp_locale.ver.to_string()); },
+		"--version" => { command = CommandsOptions::Version(mdp_locale.ver_desc.to_string()); },
+		_ => { }
+	}
+	if command.is_some() {
 		return Ok(Config {
 			command,
 			password_length: None,
 			output_file: None,
+			show_passwords: false,
+			search_options: search::Options::default(),
+			selector: Selector::Interactive,
+			field: None,
+			assume_yes: false,
+			update: false,
+			older_than: None,
+			password_stdin: false,
+			entry_type: None,
+			filters: search::Filters::default(),
+			entry_url: None,
+			list: ListOptions::default(),
+			expiry: Expiry::default(),
+			extract_to: None,
+			url_match: None,
+			secret_field: false,
 		});
 	}
-	if args[1].chars().nth(0) != Some('-') {
+	if !args[1].starts_with('-') {
 		return Err(mdp_locale.err_valid_args.to_string());
 	}
 	let mut password_length: Option<usize> = None;
+	let mut show_passwords = false;
+	let mut search_options = search::Options::default();
+	let mut selector = Selector::Interactive;
+	let mut field: Option<String> = None;
+	let mut assume_yes = false;
+	let mut update = false;
+	let mut older_than: Option<u64> = None;
+	let mut password_stdin = false;
+	let mut entry_type: Option<String> = None;
+	let mut filters = search::Filters::default();
+	let mut list = ListOptions::default();
+	let mut expiry = Expiry::default();
+	let mut extract_to: Option<String> = None;
+	let mut url: Option<String> = None;
+	let mut url_match: Option<domain::Match> = None;
+	let mut secret_field = false;
 	let mut indices_utilises = vec![0];
 
 	let max_index = args.len();
@@ -109,6 +253,200 @@ pub fn parse_args(mdp_locale: &LangStrings) -> Result<Config, String> {
 				}
 			}
 
+			// --- Option d'affichage des mots de passe (-s/--show) ---
+			"-s" | "--show" => {
+				show_passwords = true;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+
+			// --- Option de recherche sensible aux accents (--accents) ---
+			"--accents" => {
+				search_options.exact_accents = true;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+
+			// --- Option de recherche par expression régulière (--regex) ---
+			"--regex" => {
+				search_options.regex = true;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+
+			// --- Sélection non interactive (--index N, --first, --exact <titre>) ---
+			"--index" => {
+				if selector != Selector::Interactive { return Err(mdp_locale.err_select_1fois.to_string()); }
+				if current_index + 1 >= max_index { return Err(format!("--index : {}", mdp_locale.manque_args)); }
+				match args[current_index + 1].parse::<usize>() {
+					Ok(n) if n > 0 => { selector = Selector::Index(n); }
+					_ => return Err(mdp_locale.err_index_int.to_string()),
+				}
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--first" => {
+				if selector != Selector::Interactive { return Err(mdp_locale.err_select_1fois.to_string()); }
+				selector = Selector::First;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+			"--exact" => {
+				if selector != Selector::Interactive { return Err(mdp_locale.err_select_1fois.to_string()); }
+				if current_index + 1 >= max_index { return Err(format!("--exact : {}", mdp_locale.manque_args)); }
+				selector = Selector::Exact(args[current_index + 1].clone());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+
+			// --- Confirmation automatique (-y/--yes) ---
+			"-y" | "--yes" => {
+				assume_yes = true;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+
+			// --- Mise à jour d'une entrée existante par -a/-n (--update) ---
+			"--update" => {
+				update = true;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+
+			// --- Âge minimal pour --empty-trash (--older-than <jours>) ---
+			"--older-than" => {
+				if current_index + 1 >= max_index { return Err(format!("--older-than : {}", mdp_locale.manque_args)); }
+				match args[current_index + 1].parse::<u64>() {
+					Ok(jours) => { older_than = Some(jours); }
+					Err(_) => return Err(mdp_locale.err_days_int.to_string()),
+				}
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+
+			// --- Champ à extraire avec -g/--get (--field <nom>) ---
+			"--field" => {
+				if current_index + 1 >= max_index { return Err(format!("--field : {}", mdp_locale.manque_args)); }
+				let nom = &args[current_index + 1];
+				// Tout nom valide peut être un champ personnalisé
+				if !GET_FIELDS.contains(&nom.as_str()) && !template::is_field(nom) && !entry::is_field_name(nom) {
+					return Err(mdp_locale.err_bad_field.replace("{1}", nom).replace("{2}", &GET_FIELDS.join(", ")));
+				}
+				field = Some(nom.clone());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+
+			// --- Étiquette et dossier (--tag <nom>, répétable; --folder <chemin>) ---
+			"--tag" => {
+				if current_index + 1 >= max_index { return Err(format!("--tag : {}", mdp_locale.manque_args)); }
+				let nom = args[current_index + 1].trim();
+				if nom.is_empty() || nom.contains(',') { return Err(mdp_locale.err_bad_tag.replace("{1}", nom)); }
+				filters.tags.push(nom.to_string());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--folder" => {
+				if current_index + 1 >= max_index { return Err(format!("--folder : {}", mdp_locale.manque_args)); }
+				let chemin = entry::clean_folder(&args[current_index + 1]);
+				if chemin.is_empty() { return Err(mdp_locale.err_bad_folder.replace("{1}", &args[current_index + 1])); }
+				filters.folder = Some(chemin);
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+
+			// --- Présentation de --list (--columns a,b; --sort <clé>; --reverse; --tree) ---
+			"--columns" => {
+				if current_index + 1 >= max_index { return Err(format!("--columns : {}", mdp_locale.manque_args)); }
+				for nom in args[current_index + 1].split(',').map(str::trim).filter(|n| !n.is_empty()) {
+					if !LIST_COLUMNS.contains(&nom) {
+						return Err(mdp_locale.err_bad_column.replace("{1}", nom).replace("{2}", &LIST_COLUMNS.join(", ")));
+					}
+					if !list.columns.iter().any(|c| c == nom) { list.columns.push(nom.to_string()); }
+				}
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--sort" => {
+				if current_index + 1 >= max_index { return Err(format!("--sort : {}", mdp_locale.manque_args)); }
+				let nom = &args[current_index + 1];
+				if !LIST_SORTS.contains(&nom.as_str()) {
+					return Err(mdp_locale.err_bad_sort.replace("{1}", nom).replace("{2}", &LIST_SORTS.join(", ")));
+				}
+				list.sort = Some(nom.clone());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--reverse" => {
+				list.reverse = true;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+			"--tree" => {
+				list.tree = true;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+
+			// --- Échéance (--expires AAAA-MM-JJ, --max-age <jours>) ---
+			"--expires" => {
+				if current_index + 1 >= max_index { return Err(format!("--expires : {}", mdp_locale.manque_args)); }
+				match date::parse_day(&args[current_index + 1]) {
+					Some(jour) => { expiry.expires = Some(jour); }
+					None => return Err(mdp_locale.err_bad_date.replace("{1}", &args[current_index + 1])),
+				}
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--max-age" => {
+				if current_index + 1 >= max_index { return Err(format!("--max-age : {}", mdp_locale.manque_args)); }
+				match args[current_index + 1].parse::<u64>() {
+					Ok(jours) if jours > 0 => { expiry.max_age = Some(jours); }
+					_ => return Err(mdp_locale.err_days_int.to_string()),
+				}
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+
+			// --- Destination de --extract (--to <chemin>) ---
+			"--to" => {
+				if current_index + 1 >= max_index { return Err(format!("--to : {}", mdp_locale.manque_args)); }
+				extract_to = Some(args[current_index + 1].clone());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+
+			// --- Degré de correspondance de --url (--match domain|subdomain|exact) ---
+			"--match" => {
+				if current_index + 1 >= max_index { return Err(format!("--match : {}", mdp_locale.manque_args)); }
+				let nom = &args[current_index + 1];
+				match domain::Match::from_name(nom) {
+					Some(niveau) => { url_match = Some(niveau); }
+					None => return Err(mdp_locale.err_bad_match.replace("{1}", nom).replace("{2}", &domain::Match::NAMES.join(", "))),
+				}
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+
+			// --- Champ personnalisé masqué (--secret) ---
+			"--secret" => {
+				secret_field = true;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+
 			// --- Options principales (mutuellement exclusives) ---
 			"-f" | "--find" => {
 				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
@@ -128,13 +466,219 @@ pub fn parse_args(mdp_locale: &LangStrings) -> Result<Config, String> {
 			}
 			"-a" | "--add" => {
 				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
-				if current_index + 2 >= max_index { return Err(format!("-a/--add : {}", mdp_locale.manque_args)); }
-				command = CommandsOptions::Add(args[current_index + 1].clone(), args[current_index + 2].clone());
+				indices_utilises.push(current_index);
+				// Le dernier argument qui ressemble au fichier mdp n'est ni une description ni un mot de passe
+				let ressemble_fichier = |i: usize| i == max_index - 1 && (args[i].ends_with(".bin") || args[i].contains('/'));
+				let desc = args.get(current_index + 1)
+					.filter(|a| !a.starts_with('-') && !ressemble_fichier(current_index + 1));
+				let Some(desc) = desc else {
+					command = CommandsOptions::Wizard;
+					current_index += 1;
+					continue;
+				};
+				indices_utilises.push(current_index + 1);
+				// Ancienne forme '-a <desc> <mot de passe>': l'argument suivant est toujours le mot de passe,
+				// même s'il commence par '-', sauf si c'est une option de -a. En dernier, un fichier existant
+				// ou un nom en '.bin' est refusé plutôt que d'enregistrer le fichier mdp comme mot de passe.
+				let pw = args.get(current_index + 2)
+					.filter(|a| !ADD_OPTIONS.contains(&a.as_str()));
+				if let Some(a) = pw && current_index + 2 == max_index - 1 && (a.ends_with(".bin") || Path::new(a).is_file()) {
+					return Err(mdp_locale.err_pw_vault.replace("{1}", a));
+				}
+				if pw.is_some() { indices_utilises.push(current_index + 2); }
+				command = CommandsOptions::Add(desc.clone(), pw.cloned());
+				current_index += if pw.is_some() { 3 } else { 2 };
+			}
+			"--type" => {
+				if current_index + 1 >= max_index { return Err(format!("--type : {}", mdp_locale.manque_args)); }
+				let nom = &args[current_index + 1];
+				if template::find(nom).is_none() {
+					return Err(mdp_locale.err_bad_type.replace("{1}", nom).replace("{2}", &template::names().join(", ")));
+				}
+				entry_type = Some(nom.clone());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--password-stdin" => {
+				password_stdin = true;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+			"-g" | "--get" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				if current_index + 1 >= max_index { return Err(format!("-g/--get : {}", mdp_locale.manque_args)); }
+				command = CommandsOptions::Get(args[current_index + 1].clone());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--trash" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				command = CommandsOptions::Trash;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+			"--undelete" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				if current_index + 1 >= max_index { return Err(format!("--undelete : {}", mdp_locale.manque_args)); }
+				command = CommandsOptions::Undelete(args[current_index + 1].clone());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--otp" | "--otp-set" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				if current_index + 1 >= max_index { return Err(format!("{arg} : {}", mdp_locale.manque_args)); }
+				let motif = args[current_index + 1].clone();
+				command = if arg == "--otp" { CommandsOptions::Otp(motif) } else { CommandsOptions::OtpSet(motif) };
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--qr" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				if current_index + 1 >= max_index { return Err(format!("--qr : {}", mdp_locale.manque_args)); }
+				command = CommandsOptions::Qr(args[current_index + 1].clone());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--import-otp" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				command = CommandsOptions::ImportOtp;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+			"--empty-trash" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				command = CommandsOptions::EmptyTrash;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+			"--undo" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				indices_utilises.push(current_index);
+				current_index += 1;
+				// Nombre d'opérations optionnel, 1 par défaut
+				let mut n = 1;
+				if let Some(Ok(nombre)) = args.get(current_index).map(|a| a.parse::<usize>()) {
+					if nombre == 0 { return Err(mdp_locale.err_index_int.to_string()); }
+					n = nombre;
+					indices_utilises.push(current_index);
+					current_index += 1;
+				}
+				command = CommandsOptions::Undo(n);
+			}
+			"--dedupe" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				command = CommandsOptions::Dedupe;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+			"--list" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				indices_utilises.push(current_index);
+				// Motif optionnel; le dernier argument qui ressemble au fichier mdp n'en est pas un
+				let motif = args.get(current_index + 1).filter(|a| {
+					let fichier = current_index + 1 == max_index - 1 && (a.ends_with(".bin") || a.contains('/'));
+					!a.starts_with('-') && !fichier
+				});
+				if motif.is_some() { indices_utilises.push(current_index + 1); }
+				command = CommandsOptions::List(motif.cloned().unwrap_or_default());
+				current_index += if motif.is_some() { 2 } else { 1 };
+			}
+			"--due" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				indices_utilises.push(current_index);
+				current_index += 1;
+				// Nombre de jours optionnel; 0: seulement les entrées déjà expirées
+				let mut jours = DUE_DAYS;
+				if let Some(Ok(nombre)) = args.get(current_index).map(|a| a.parse::<u64>()) {
+					jours = nombre;
+					indices_utilises.push(current_index);
+					current_index += 1;
+				}
+				command = CommandsOptions::Due(jours);
+			}
+			"--set-expiry" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				if current_index + 1 >= max_index { return Err(format!("--set-expiry : {}", mdp_locale.manque_args)); }
+				command = CommandsOptions::SetExpiry(args[current_index + 1].clone());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--attach" | "--detach" | "--extract" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				if current_index + 2 >= max_index { return Err(format!("{arg} : {}", mdp_locale.manque_args)); }
+				let motif = args[current_index + 1].clone();
+				let nom = args[current_index + 2].clone();
+				command = match arg.as_str() {
+					"--attach" => CommandsOptions::Attach(motif, nom),
+					"--detach" => CommandsOptions::Detach(motif, nom),
+					_ => CommandsOptions::Extract(motif, nom),
+				};
 				indices_utilises.push(current_index);
 				indices_utilises.push(current_index + 1);
 				indices_utilises.push(current_index + 2);
 				current_index += 3;
 			}
+			// --- Recherche par URL, ou URL de l'entrée créée par -a/-n (voir plus bas) ---
+			"--url" => {
+				if url.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				if current_index + 1 >= max_index { return Err(format!("--url : {}", mdp_locale.manque_args)); }
+				let adresse = &args[current_index + 1];
+				if domain::host(adresse).is_none() { return Err(mdp_locale.err_bad_url.replace("{1}", adresse)); }
+				url = Some(adresse.clone());
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				current_index += 2;
+			}
+			"--set-field" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				if current_index + 2 >= max_index { return Err(format!("--set-field : {}", mdp_locale.manque_args)); }
+				// '<nom>=<valeur>', ou '<nom>' seul: la valeur sera demandée
+				let (nom, valeur) = match args[current_index + 2].split_once('=') {
+					Some((nom, valeur)) => (nom.to_string(), Some(valeur.to_string())),
+					None => (args[current_index + 2].clone(), None),
+				};
+				if !entry::is_field_name(&nom) { return Err(mdp_locale.err_bad_field_name.replace("{1}", &nom)); }
+				// Les champs descriptifs et ceux des types se modifient tels quels; les autres champs connus sont réservés
+				let modifiable = entry::EDITABLE.contains(&nom.as_str()) || template::is_field(&nom);
+				if !modifiable && (GET_FIELDS.contains(&nom.as_str()) || entry::is_builtin(&nom)) {
+					return Err(mdp_locale.err_field_reserved.replace("{1}", &nom));
+				}
+				command = CommandsOptions::SetField(args[current_index + 1].clone(), nom, valeur);
+				indices_utilises.push(current_index);
+				indices_utilises.push(current_index + 1);
+				indices_utilises.push(current_index + 2);
+				current_index += 3;
+			}
+			"--attachments" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				indices_utilises.push(current_index);
+				// Motif optionnel, comme pour --list
+				let motif = args.get(current_index + 1).filter(|a| {
+					let fichier = current_index + 1 == max_index - 1 && (a.ends_with(".bin") || a.contains('/'));
+					!a.starts_with('-') && !fichier
+				});
+				if motif.is_some() { indices_utilises.push(current_index + 1); }
+				command = CommandsOptions::Attachments(motif.cloned().unwrap_or_default());
+				current_index += if motif.is_some() { 2 } else { 1 };
+			}
+			"--tags" | "--folders" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				command = if arg == "--tags" { CommandsOptions::Tags } else { CommandsOptions::Folders };
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
+			"--log" => {
+				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
+				command = CommandsOptions::Log;
+				indices_utilises.push(current_index);
+				current_index += 1;
+			}
 			"-n" | "--new" => {
 				if command.is_some() { return Err(mdp_locale.comm_mut_excl.to_string()); }
 				if current_index + 1 >= max_index { return Err(format!("-n/--new : {}", mdp_locale.manque_args)); }
@@ -165,6 +709,64 @@ pub fn parse_args(mdp_locale: &LangStrings) -> Result<Config, String> {
 			return Err(format!("{} : {}",mdp_locale.err_bad_arg, args[i]));
 		}
 	}
+	// Le classement suivi par --index ne dépend pas de l'usage, qui change à chaque sélection
+	search_options.stable = matches!(selector, Selector::Index(_));
+	// --url seul cherche par adresse; avec -a/-n, c'est l'URL de l'entrée créée
+	let mut entry_url: Option<String> = None;
+	if let Some(adresse) = url {
+		match command {
+			CommandsOptions::None => { command = CommandsOptions::Url(adresse); }
+			CommandsOptions::Add(_, _) | CommandsOptions::New(_) => { entry_url = Some(adresse); }
+			_ => return Err(mdp_locale.comm_mut_excl.to_string()),
+		}
+	}
+
+	if password_stdin && !matches!(command, CommandsOptions::Add(_, None)) {
+		return Err(mdp_locale.err_pw_stdin.to_string());
+	}
+	if entry_type.is_some() && !matches!(command, CommandsOptions::Add(_, _) | CommandsOptions::Wizard) {
+		return Err(mdp_locale.err_type_add.to_string());
+	}
+	if list != ListOptions::default() && !matches!(command, CommandsOptions::List(_)) {
+		return Err(mdp_locale.err_list_opt.to_string());
+	}
+	if update && !matches!(command, CommandsOptions::Add(_, _) | CommandsOptions::Wizard | CommandsOptions::New(_)) {
+		return Err(mdp_locale.err_update_add.to_string());
+	}
+	if field.is_some() && !matches!(command, CommandsOptions::Get(_) | CommandsOptions::Qr(_)) {
+		return Err(mdp_locale.err_field_cmd.to_string());
+	}
+	if secret_field && !matches!(command, CommandsOptions::SetField(_, _, _)) {
+		return Err(mdp_locale.err_secret_field.to_string());
+	}
+	if let CommandsOptions::SetField(_, nom, _) = &command && secret_field && template::is_field(nom) {
+		return Err(mdp_locale.err_secret_type.replace("{1}", nom));
+	}
+	if let CommandsOptions::SetField(_, nom, valeur) = &command && entry::EDITABLE.contains(&nom.as_str()) {
+		if secret_field { return Err(mdp_locale.err_secret_builtin.replace("{1}", nom)); }
+		if let Some(v) = valeur.as_deref().filter(|v| nom == entry::URL && !v.is_empty() && domain::host(v).is_none()) {
+			return Err(mdp_locale.err_bad_url.replace("{1}", v));
+		}
+	}
+	if url_match.is_some() && !matches!(command, CommandsOptions::Url(_)) {
+		return Err(mdp_locale.err_match_url.to_string());
+	}
+	if extract_to.is_some() && !matches!(command, CommandsOptions::Extract(_, _)) {
+		return Err(mdp_locale.err_to_extract.to_string());
+	}
+	if older_than.is_some() && !matches!(command, CommandsOptions::EmptyTrash | CommandsOptions::List(_)) {
+		return Err(mdp_locale.err_older_than.to_string());
+	}
+	if expiry != Expiry::default()
+		&& !matches!(command, CommandsOptions::Add(_, _) | CommandsOptions::Wizard | CommandsOptions::New(_) | CommandsOptions::SetExpiry(_)) {
+		return Err(mdp_locale.err_expiry_cmd.to_string());
+	}
+	// Filtres des commandes qui cherchent, ou classement des entrées créées
+	let classe = command.pattern().is_some()
+		|| matches!(command, CommandsOptions::Add(_, _) | CommandsOptions::Wizard | CommandsOptions::New(_));
+	if (!filters.tags.is_empty() || filters.folder.is_some()) && !classe {
+		return Err(mdp_locale.err_filter_cmd.to_string());
+	}
 
 	// 4. Gestion de la commande par défaut
 	if let CommandsOptions::None = command {
@@ -180,5 +782,21 @@ pub fn parse_args(mdp_locale: &LangStrings) -> Result<Config, String> {
 		command,
 		password_length,
 		output_file,
+		show_passwords,
+		search_options,
+		selector,
+		field,
+		assume_yes,
+		update,
+		older_than,
+		password_stdin,
+		entry_type,
+		filters,
+		entry_url,
+		list,
+		expiry,
+		extract_to,
+		url_match,
+		secret_field,
 	})
 }
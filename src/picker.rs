@@ -0,0 +1,259 @@
+// picker.rs
+
+use std::io::{self, Read, Write};
+use std::os::unix::io::AsRawFd;
+use std::time::{Duration, Instant};
+
+use crate::get_pw;
+use crate::locale::LangStrings;
+use crate::search;
+
+// --- 1. Séquences du terminal ---
+
+const ECRAN_ALT: &str = "\x1b[?1049h\x1b[?25l";
+const ECRAN_NORMAL: &str = "\x1b[?25h\x1b[?1049l";
+const EFFACE: &str = "\x1b[H\x1b[2J";
+const INVERSE: &str = "\x1b[7m";
+const NORMAL: &str = "\x1b[0m";
+
+enum Touche {
+	Haut,
+	Bas,
+	PageHaut,
+	PageBas,
+	Entree,
+	Echap,
+	Tab,
+	Espace,
+	Efface,
+	Car(char),
+	Autre,
+}
+
+// --- 2. Lecture du clavier ---
+
+fn lire_octet(handle: &mut io::StdinLock) -> Option<u8> {
+	let mut buffer = [0u8; 1];
+	match handle.read(&mut buffer) {
+		Ok(1) => Some(buffer[0]),
+		_ => None,		// Délai écoulé (VTIME) sans donnée
+	}
+}
+
+// Une lecture vide revient normalement après le délai (VTIME, 1/10 s). Revenue aussitôt,
+// plusieurs fois de suite, elle signale la fin du flux (terminal fermé).
+const LECTURE_IMMEDIATE: Duration = Duration::from_millis(50);
+const FIN_DE_FLUX: usize = 3;
+
+// Attend le prochain octet; None à la fin du flux ou sur une erreur de lecture.
+fn attendre_octet(handle: &mut io::StdinLock) -> Option<u8> {
+	let mut vides = 0;
+	loop {
+		let debut = Instant::now();
+		let mut buffer = [0u8; 1];
+		match handle.read(&mut buffer) {
+			Ok(1) => return Some(buffer[0]),
+			Ok(_) if debut.elapsed() >= LECTURE_IMMEDIATE => vides = 0,
+			Ok(_) => {
+				vides += 1;
+				if vides >= FIN_DE_FLUX { return None; }
+			}
+			Err(e) if e.kind() == io::ErrorKind::Interrupted => { }
+			Err(_) => return None,
+		}
+	}
+}
+
+fn lire_touche(handle: &mut io::StdinLock) -> Touche {
+	// Plus rien à lire: on abandonne comme avec Esc
+	let Some(octet) = attendre_octet(handle) else { return Touche::Echap };
+	match octet {
+		b'\n' | 0x0D => Touche::Entree,
+		b'\t' => Touche::Tab,
+		b' ' => Touche::Espace,
+		0x7F | 0x08 => Touche::Efface,
+		0x03 => Touche::Echap,		// Ctrl-C
+		0x10 => Touche::Haut,		// Ctrl-P
+		0x0E => Touche::Bas,		// Ctrl-N
+		0x1B => {
+			// Esc seul si rien ne suit dans le délai, sinon séquence ESC [ x
+			match lire_octet(handle) {
+				None => Touche::Echap,
+				Some(b'[') | Some(b'O') => match lire_octet(handle) {
+					Some(b'A') => Touche::Haut,
+					Some(b'B') => Touche::Bas,
+					Some(b'5') => { lire_octet(handle); Touche::PageHaut }
+					Some(b'6') => { lire_octet(handle); Touche::PageBas }
+					_ => Touche::Autre,
+				},
+				Some(_) => Touche::Autre,
+			}
+		}
+		c if c < 0x20 => Touche::Autre,
+		c => {
+			// Caractère UTF-8 sur plusieurs octets
+			let longueur = match c {
+				0xC0..=0xDF => 2,
+				0xE0..=0xEF => 3,
+				0xF0..=0xF7 => 4,
+				_ => 1,
+			};
+			let mut octets = vec![c];
+			for _ in 1..longueur {
+				if let Some(o) = lire_octet(handle) { octets.push(o); }
+			}
+			match String::from_utf8(octets).ok().and_then(|s| s.chars().next()) {
+				Some(ch) => Touche::Car(ch),
+				None => Touche::Autre,
+			}
+		}
+	}
+}
+
+// --- 3. Affichage ---
+
+struct Etat<'a> {
+	prompt: &'a str,
+	labels: &'a [String],
+	secrets: &'a [String],
+	filtre: String,
+	visibles: Vec<usize>,
+	courant: usize,
+	decalage: usize,
+	revele: Option<usize>,
+	marques: Option<Vec<bool>>,		// Sélection multiple seulement
+}
+
+impl Etat<'_> {
+	fn filtrer(&mut self) {
+		if self.filtre.is_empty() {
+			self.visibles = (0..self.labels.len()).collect();
+		}
+		else {
+			let mut scores: Vec<(usize, i64)> = self.labels.iter().enumerate()
+				.filter_map(|(i, l)| search::fuzzy(&self.filtre, l).map(|s| (i, s)))
+				.collect();
+			scores.sort_by_key(|&(_, s)| std::cmp::Reverse(s));
+			self.visibles = scores.into_iter().map(|(i, _)| i).collect();
+		}
+		self.courant = 0;
+		self.decalage = 0;
+		self.revele = None;
+	}
+
+	fn afficher(&mut self, loc: &LangStrings, hauteur: usize) {
+		// Garder la ligne courante dans la fenêtre
+		if self.courant < self.decalage { self.decalage = self.courant; }
+		if self.courant >= self.decalage + hauteur { self.decalage = self.courant + 1 - hauteur; }
+
+		let mut ecran = String::from(EFFACE);
+		let aide = if self.marques.is_some() { loc.picker_help_multi } else { loc.picker_help };
+		ecran.push_str(&format!("{}\n{}\n", self.prompt, aide));
+		ecran.push_str(&format!("{} {}\n", loc.picker_filter, self.filtre));
+		for (n, &i) in self.visibles.iter().enumerate().skip(self.decalage).take(hauteur) {
+			let mut ligne = match &self.marques {
+				Some(m) if m[i] => format!("[x] {}", self.labels[i]),
+				Some(_) => format!("[ ] {}", self.labels[i]),
+				None => self.labels[i].clone(),
+			};
+			if self.revele == Some(i) {
+				ligne.push_str(" ==> ");
+				ligne.push_str(&self.secrets[i]);
+			}
+			if n == self.courant {
+				ecran.push_str(&format!("{INVERSE}> {ligne}{NORMAL}\n"));
+			}
+			else {
+				ecran.push_str(&format!("  {ligne}\n"));
+			}
+		}
+		print!("{ecran}");
+		let _ = io::stdout().flush();
+	}
+}
+
+// --- 4. Fonction principale ---
+
+/// Vrai si le sélecteur plein écran peut être utilisé (stdin et stdout sont des terminaux).
+pub fn available() -> bool {
+	get_pw::is_tty(io::stdin().as_raw_fd()) && get_pw::is_tty(io::stdout().as_raw_fd())
+}
+
+/// Sélecteur plein écran: flèches pour se déplacer, saisie pour filtrer,
+/// Tab pour révéler le secret de la ligne courante, Entrée pour choisir, Esc pour annuler.
+/// Retourne l'index (dans 'labels') de la ligne choisie.
+pub fn pick(prompt: &str, labels: &[String], secrets: &[String], loc: &LangStrings) -> Option<usize> {
+	run(prompt, labels, secrets, loc, false).and_then(|choix| choix.first().copied())
+}
+
+/// Comme 'pick', mais Espace marque plusieurs lignes. Sans marque, Entrée choisit la ligne courante.
+/// Retourne les index (dans 'labels') choisis, en ordre croissant.
+pub fn pick_many(prompt: &str, labels: &[String], secrets: &[String], loc: &LangStrings) -> Option<Vec<usize>> {
+	run(prompt, labels, secrets, loc, true)
+}
+
+fn run(prompt: &str, labels: &[String], secrets: &[String], loc: &LangStrings, multi: bool) -> Option<Vec<usize>> {
+	let stdin = io::stdin();
+	let _guard = get_pw::raw_mode(stdin.as_raw_fd(), true)?;
+	let mut handle = stdin.lock();
+
+	let hauteur = get_pw::terminal_rows().saturating_sub(4).max(1);
+	let mut etat = Etat {
+		prompt, labels, secrets,
+		filtre: String::new(),
+		visibles: Vec::new(),
+		courant: 0,
+		decalage: 0,
+		revele: None,
+		marques: multi.then(|| vec![false; labels.len()]),
+	};
+	etat.filtrer();
+
+	print!("{ECRAN_ALT}");
+	let choix = loop {
+		etat.afficher(loc, hauteur);
+		match lire_touche(&mut handle) {
+			Touche::Haut => { etat.courant = etat.courant.saturating_sub(1); }
+			Touche::Bas => {
+				if etat.courant + 1 < etat.visibles.len() { etat.courant += 1; }
+			}
+			Touche::PageHaut => { etat.courant = etat.courant.saturating_sub(hauteur); }
+			Touche::PageBas => {
+				etat.courant = (etat.courant + hauteur).min(etat.visibles.len().saturating_sub(1));
+			}
+			Touche::Tab => {
+				let i = etat.visibles.get(etat.courant).copied();
+				etat.revele = if etat.revele == i { None } else { i };
+			}
+			Touche::Entree => {
+				let marquees: Vec<usize> = match &etat.marques {
+					Some(m) => (0..m.len()).filter(|&i| m[i]).collect(),
+					None => Vec::new(),
+				};
+				if !marquees.is_empty() { break Some(marquees); }
+				if let Some(&i) = etat.visibles.get(etat.courant) { break Some(vec![i]); }
+			}
+			Touche::Espace => {
+				let courante = etat.visibles.get(etat.courant).copied();
+				match (&mut etat.marques, courante) {
+					(Some(m), Some(i)) => {
+						m[i] = !m[i];
+						if etat.courant + 1 < etat.visibles.len() { etat.courant += 1; }
+					}
+					// Sélection simple: l'espace fait partie du filtre
+					(None, _) => { etat.filtre.push(' '); etat.filtrer(); }
+					_ => { }
+				}
+			}
+			Touche::Echap => break None,
+			Touche::Efface => {
+				if etat.filtre.pop().is_some() { etat.filtrer(); }
+			}
+			Touche::Car(c) => { etat.filtre.push(c); etat.filtrer(); }
+			Touche::Autre => { }
+		}
+	};
+	print!("{ECRAN_NORMAL}");
+	let _ = io::stdout().flush();
+	choix
+}
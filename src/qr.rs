@@ -0,0 +1,452 @@
+// qr.rs
+
+// Encodeur de codes QR (ISO/IEC 18004), sans dépendance:
+//     mode octet, correction d'erreur M (~15 %), versions 1 à 40, choix du meilleur masque.
+// Le code est affiché au terminal avec des demi-blocs Unicode: un caractère = deux modules en hauteur.
+
+// --- 1. Tables (niveau de correction M) ---
+
+// Octets de correction par bloc et nombre de blocs, selon la version (index 0 inutilisé)
+const ECC_PAR_BLOC: [usize; 41] = [
+	0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26,
+	26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+];
+const NB_BLOCS: [usize; 41] = [
+	0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16,
+	17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+];
+const BITS_NIVEAU_M: u32 = 0b00;
+
+// Demi-blocs: (haut foncé, bas foncé)
+const PLEIN: char = '█';
+const HAUT: char = '▀';
+const BAS: char = '▄';
+// Modules foncés en noir sur fond blanc, quelle que soit la couleur du terminal
+const COULEURS: &str = "\x1b[30;47m";
+const NORMAL: &str = "\x1b[0m";
+const MARGE: usize = 4;		// Zone de silence exigée autour du code, en modules
+
+// --- 2. Capacité ---
+
+/// Modules disponibles pour les données (et la correction) d'une version.
+fn modules_donnees(version: usize) -> usize {
+	let mut total = (16 * version + 128) * version + 64;
+	if version >= 2 {
+		let nb_align = version / 7 + 2;
+		total -= (25 * nb_align - 10) * nb_align - 55;
+		if version >= 7 { total -= 36; }
+	}
+	total
+}
+
+fn octets_donnees(version: usize) -> usize {
+	modules_donnees(version) / 8 - ECC_PAR_BLOC[version] * NB_BLOCS[version]
+}
+
+fn bits_longueur(version: usize) -> usize {
+	if version <= 9 { 8 } else { 16 }
+}
+
+// --- 3. Reed-Solomon sur GF(256), polynôme 0x11D ---
+
+fn gf_mul(x: u8, y: u8) -> u8 {
+	let mut z: u32 = 0;
+	for i in (0..8).rev() {
+		z = (z << 1) ^ ((z >> 7) * 0x11D);
+		z ^= ((y as u32 >> i) & 1) * x as u32;
+	}
+	z as u8
+}
+
+fn rs_diviseur(degre: usize) -> Vec<u8> {
+	let mut resultat = vec![0u8; degre];
+	resultat[degre - 1] = 1;
+	let mut racine: u8 = 1;
+	for _ in 0..degre {
+		for j in 0..degre {
+			resultat[j] = gf_mul(resultat[j], racine);
+			if j + 1 < degre { resultat[j] ^= resultat[j + 1]; }
+		}
+		racine = gf_mul(racine, 0x02);
+	}
+	resultat
+}
+
+fn rs_reste(donnees: &[u8], diviseur: &[u8]) -> Vec<u8> {
+	let mut reste = vec![0u8; diviseur.len()];
+	for &octet in donnees {
+		let facteur = octet ^ reste.remove(0);
+		reste.push(0);
+		for (r, &d) in reste.iter_mut().zip(diviseur) {
+			*r ^= gf_mul(d, facteur);
+		}
+	}
+	reste
+}
+
+/// Découpe les données en blocs, ajoute la correction de chacun et entrelace le tout.
+fn ajoute_correction(donnees: &[u8], version: usize) -> Vec<u8> {
+	let nb_blocs = NB_BLOCS[version];
+	let ecc = ECC_PAR_BLOC[version];
+	let total = modules_donnees(version) / 8;
+	let nb_courts = nb_blocs - total % nb_blocs;
+	let long_court = total / nb_blocs;
+	let diviseur = rs_diviseur(ecc);
+
+	let mut blocs: Vec<Vec<u8>> = Vec::with_capacity(nb_blocs);
+	let mut k = 0;
+	for i in 0..nb_blocs {
+		let longueur = long_court - ecc + usize::from(i >= nb_courts);
+		let mut bloc = donnees[k..k + longueur].to_vec();
+		k += longueur;
+		let correction = rs_reste(&bloc, &diviseur);
+		if i < nb_courts { bloc.push(0); }		// Case vide, sautée à l'entrelacement
+		bloc.extend(correction);
+		blocs.push(bloc);
+	}
+
+	let mut resultat = Vec::with_capacity(total);
+	for i in 0..blocs[0].len() {
+		for (j, bloc) in blocs.iter().enumerate() {
+			if i != long_court - ecc || j >= nb_courts {
+				resultat.push(bloc[i]);
+			}
+		}
+	}
+	resultat
+}
+
+// --- 4. Matrice ---
+
+struct Matrice {
+	taille: usize,
+	modules: Vec<Vec<bool>>,
+	fonction: Vec<Vec<bool>>,	// Motifs fixes: ni données ni masque
+}
+
+impl Matrice {
+	fn new(version: usize) -> Self {
+		let taille = version * 4 + 17;
+		Self { taille, modules: vec![vec![false; taille]; taille], fonction: vec![vec![false; taille]; taille] }
+	}
+
+	fn fixe(&mut self, x: usize, y: usize, fonce: bool) {
+		self.modules[y][x] = fonce;
+		self.fonction[y][x] = true;
+	}
+
+	fn motifs(&mut self, version: usize) {
+		let n = self.taille;
+		// Lignes de synchronisation
+		for i in 0..n {
+			self.fixe(6, i, i % 2 == 0);
+			self.fixe(i, 6, i % 2 == 0);
+		}
+		// Repères des trois coins (avec leur séparateur)
+		for (cx, cy) in [(3, 3), (n - 4, 3), (3, n - 4)] {
+			for dy in -4i32..=4 {
+				for dx in -4i32..=4 {
+					let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+					if (0..n as i32).contains(&x) && (0..n as i32).contains(&y) {
+						let distance = dx.abs().max(dy.abs());
+						self.fixe(x as usize, y as usize, distance != 2 && distance != 4);
+					}
+				}
+			}
+		}
+		// Motifs d'alignement, sauf là où ils chevauchent les repères
+		let positions = positions_alignement(version, n);
+		let dernier = positions.len().saturating_sub(1);
+		for (i, &cy) in positions.iter().enumerate() {
+			for (j, &cx) in positions.iter().enumerate() {
+				// Les trois coins occupés par un repère: (0, 0), (0, dernier) et (dernier, 0)
+				if i.min(j) == 0 && (i.max(j) == 0 || i.max(j) == dernier) { continue; }
+				for dy in -2i32..=2 {
+					for dx in -2i32..=2 {
+						let (x, y) = ((cx as i32 + dx) as usize, (cy as i32 + dy) as usize);
+						self.fixe(x, y, dx.abs().max(dy.abs()) != 1);
+					}
+				}
+			}
+		}
+		// Zones du format (écrites plus tard) et information de version
+		self.format(0);
+		if version >= 7 {
+			let mut reste = version as u32;
+			for _ in 0..12 {
+				reste = (reste << 1) ^ ((reste >> 11) * 0x1F25);
+			}
+			let bits = (version as u32) << 12 | reste;
+			for i in 0..18 {
+				let fonce = (bits >> i) & 1 != 0;
+				let (a, b) = (n - 11 + i % 3, i / 3);
+				self.fixe(a, b, fonce);
+				self.fixe(b, a, fonce);
+			}
+		}
+	}
+
+	/// Écrit les deux copies de l'information de format (niveau M, masque).
+	fn format(&mut self, masque: u32) {
+		let n = self.taille;
+		let donnees = BITS_NIVEAU_M << 3 | masque;
+		let mut reste = donnees;
+		for _ in 0..10 {
+			reste = (reste << 1) ^ ((reste >> 9) * 0x537);
+		}
+		let bits = (donnees << 10 | reste) ^ 0x5412;
+		let bit = |i: usize| (bits >> i) & 1 != 0;
+
+		for i in 0..=5 { self.fixe(8, i, bit(i)); }
+		self.fixe(8, 7, bit(6));
+		self.fixe(8, 8, bit(7));
+		self.fixe(7, 8, bit(8));
+		for i in 9..15 { self.fixe(14 - i, 8, bit(i)); }
+
+		for i in 0..8 { self.fixe(n - 1 - i, 8, bit(i)); }
+		for i in 8..15 { self.fixe(8, n - 15 + i, bit(i)); }
+		self.fixe(8, n - 8, true);		// Module toujours foncé
+	}
+
+	/// Place les octets en zigzag, par paires de colonnes, de droite à gauche.
+	fn place(&mut self, octets: &[u8]) {
+		let n = self.taille;
+		let mut i = 0;
+		let mut droite = n - 1;
+		while droite >= 1 {
+			if droite == 6 { droite = 5; }		// La colonne de synchronisation est sautée
+			let montant = (droite + 1) & 2 == 0;
+			for vert in 0..n {
+				let y = if montant { n - 1 - vert } else { vert };
+				for x in [droite, droite - 1] {
+					if !self.fonction[y][x] && i < octets.len() * 8 {
+						self.modules[y][x] = (octets[i >> 3] >> (7 - (i & 7))) & 1 != 0;
+						i += 1;
+					}
+				}
+			}
+			if droite < 2 { break; }
+			droite -= 2;
+		}
+	}
+
+	/// Applique (ou retire: l'opération est sa propre inverse) un des 8 masques.
+	fn masque(&mut self, masque: u32) {
+		for y in 0..self.taille {
+			for x in 0..self.taille {
+				let inverse = match masque {
+					0 => (x + y) % 2 == 0,
+					1 => y % 2 == 0,
+					2 => x % 3 == 0,
+					3 => (x + y) % 3 == 0,
+					4 => (x / 3 + y / 2) % 2 == 0,
+					5 => x * y % 2 + x * y % 3 == 0,
+					6 => (x * y % 2 + x * y % 3) % 2 == 0,
+					_ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+				};
+				if inverse && !self.fonction[y][x] {
+					self.modules[y][x] = !self.modules[y][x];
+				}
+			}
+		}
+	}
+
+	/// Pénalité de la norme: longues suites, blocs 2x2, motifs semblables aux repères, équilibre.
+	fn penalite(&self) -> usize {
+		let n = self.taille;
+		let mut total = 0;
+		let motif = [true, false, true, true, true, false, true, false, false, false, false];
+		let mut motif_inverse = motif;
+		motif_inverse.reverse();
+
+		for sens in 0..2 {
+			for a in 0..n {
+				let ligne: Vec<bool> = (0..n)
+					.map(|b| if sens == 0 { self.modules[a][b] } else { self.modules[b][a] })
+					.collect();
+				let mut suite = 1;
+				for b in 1..=n {
+					if b < n && ligne[b] == ligne[b - 1] { suite += 1; continue; }
+					if suite >= 5 { total += suite - 2; }
+					suite = 1;
+				}
+				for fenetre in ligne.windows(motif.len()) {
+					if fenetre == motif || fenetre == motif_inverse { total += 40; }
+				}
+			}
+		}
+		for y in 0..n - 1 {
+			for x in 0..n - 1 {
+				let c = self.modules[y][x];
+				if c == self.modules[y][x + 1] && c == self.modules[y + 1][x] && c == self.modules[y + 1][x + 1] {
+					total += 3;
+				}
+			}
+		}
+		let fonces = self.modules.iter().flatten().filter(|&&m| m).count();
+		let modules = n * n;
+		let k = (fonces * 20).abs_diff(modules * 10).div_ceil(modules) - 1;
+		total + k * 10
+	}
+}
+
+fn positions_alignement(version: usize, taille: usize) -> Vec<usize> {
+	if version == 1 { return Vec::new(); }
+	let nb = version / 7 + 2;
+	let pas = if version == 32 { 26 } else { (version * 4 + nb * 2 + 1) / (nb * 2 - 2) * 2 };
+	let mut positions: Vec<usize> = (0..nb - 1).map(|i| taille - 7 - i * pas).collect();
+	positions.push(6);
+	positions.reverse();
+	positions
+}
+
+// --- 5. Fonctions principales ---
+
+/// Code QR des octets donnés: matrice de modules (vrai = foncé), ou None si trop long.
+pub fn encode(texte: &[u8]) -> Option<Vec<Vec<bool>>> {
+	let version = (1..=40).find(|&v| 4 + bits_longueur(v) + texte.len() * 8 <= octets_donnees(v) * 8)?;
+	let capacite = octets_donnees(version) * 8;
+
+	// Flux de bits: mode octet (0100), longueur, données, terminateur, remplissage
+	let mut bits: Vec<bool> = Vec::with_capacity(capacite);
+	let mut ajoute = |valeur: usize, nombre: usize| {
+		for i in (0..nombre).rev() { bits.push((valeur >> i) & 1 != 0); }
+	};
+	ajoute(0b0100, 4);
+	ajoute(texte.len(), bits_longueur(version));
+	for &octet in texte { ajoute(octet as usize, 8); }
+	let terminateur = (capacite - bits.len()).min(4);
+	bits.extend(std::iter::repeat_n(false, terminateur));
+	while !bits.len().is_multiple_of(8) { bits.push(false); }
+
+	let mut octets: Vec<u8> = bits.chunks(8)
+		.map(|c| c.iter().fold(0u8, |acc, &b| acc << 1 | u8::from(b)))
+		.collect();
+	for remplissage in [0xEC, 0x11].into_iter().cycle() {
+		if octets.len() * 8 >= capacite { break; }
+		octets.push(remplissage);
+	}
+
+	let mut matrice = Matrice::new(version);
+	matrice.motifs(version);
+	matrice.place(&ajoute_correction(&octets, version));
+
+	// Le masque de plus faible pénalité
+	let mut meilleur = (usize::MAX, 0);
+	for masque in 0..8 {
+		matrice.masque(masque);
+		matrice.format(masque);
+		let penalite = matrice.penalite();
+		if penalite < meilleur.0 { meilleur = (penalite, masque); }
+		matrice.masque(masque);
+	}
+	matrice.masque(meilleur.1);
+	matrice.format(meilleur.1);
+	Some(matrice.modules)
+}
+
+/// Dessin du code pour le terminal, zone de silence comprise.
+pub fn to_terminal(modules: &[Vec<bool>]) -> String {
+	let n = modules.len();
+	let fonce = |x: usize, y: usize| {
+		x >= MARGE && y >= MARGE && x - MARGE < n && y - MARGE < n && modules[y - MARGE][x - MARGE]
+	};
+	let cote = n + 2 * MARGE;
+	let mut dessin = String::new();
+	for y in (0..cote).step_by(2) {
+		dessin.push_str(COULEURS);
+		for x in 0..cote {
+			dessin.push(match (fonce(x, y), fonce(x, y + 1)) {
+				(true, true) => PLEIN,
+				(true, false) => HAUT,
+				(false, true) => BAS,
+				(false, false) => ' ',
+			});
+		}
+		dessin.push_str(NORMAL);
+		dessin.push('\n');
+	}
+	dessin
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Information de format lue dans la première copie, bit 14 d'abord
+	fn format_lu(modules: &[Vec<bool>]) -> String {
+		let mut positions: Vec<(usize, usize)> = (0..6).map(|x| (x, 8)).collect();
+		positions.extend([(7, 8), (8, 8), (8, 7)]);
+		positions.extend((0..6).rev().map(|y| (8, y)));
+		positions.iter().map(|&(x, y)| if modules[y][x] { '1' } else { '0' }).collect()
+	}
+
+	#[test]
+	fn correction_reed_solomon() {
+		// 'HELLO WORLD' en version 1-M (exemple classique de la norme)
+		let donnees = [32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17];
+		assert_eq!(rs_reste(&donnees, &rs_diviseur(10)), [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+		assert_eq!(gf_mul(0x80, 0x02), 0x1D);
+	}
+
+	#[test]
+	fn capacite_niveau_m() {
+		assert_eq!(octets_donnees(1), 16);
+		assert_eq!(octets_donnees(10), 216);
+		assert_eq!(octets_donnees(40), 2334);
+		assert_eq!(encode(&[b'a'; 14]).map(|m| m.len()), Some(21));
+		assert_eq!(encode(&[b'a'; 15]).map(|m| m.len()), Some(25));
+		assert_eq!(encode(&[b'a'; 2331]).map(|m| m.len()), Some(177));
+		assert!(encode(&[b'a'; 2332]).is_none());
+	}
+
+	#[test]
+	fn information_de_format_et_de_version() {
+		let attendus = ["101010000010010", "101000100100101", "101111001111100", "101101101001011",
+						"100010111111001", "100000011001110", "100111110010111", "100101010100000"];
+		let mut matrice = Matrice::new(1);
+		for (masque, attendu) in attendus.iter().enumerate() {
+			matrice.format(masque as u32);
+			assert_eq!(format_lu(&matrice.modules), *attendu);
+		}
+		// Version 7: 000111 suivi de sa correction BCH
+		let mut matrice = Matrice::new(7);
+		matrice.motifs(7);
+		let n = matrice.taille;
+		let lu = (0..18).fold(0u32, |v, i| v | u32::from(matrice.modules[i / 3][n - 11 + i % 3]) << i);
+		assert_eq!(lu, 0x07C94);
+	}
+
+	#[test]
+	fn relecture_version_1() {
+		let texte = b"otpauth://x";
+		let modules = encode(texte).expect("assez court");
+		let format = u32::from_str_radix(&format_lu(&modules), 2).unwrap_or_default() ^ 0x5412;
+		assert_eq!(format >> 13, BITS_NIVEAU_M);
+
+		// Retire le masque, puis relit les octets dans l'ordre de placement
+		let mut matrice = Matrice::new(1);
+		matrice.motifs(1);
+		matrice.modules = modules;
+		matrice.masque(format >> 10 & 7);
+		let mut bits = Vec::new();
+		let mut droite = 20;
+		loop {
+			if droite == 6 { droite = 5; }
+			let montant = (droite + 1) & 2 == 0;
+			for vert in 0..21 {
+				let y = if montant { 20 - vert } else { vert };
+				for x in [droite, droite - 1] {
+					if !matrice.fonction[y][x] { bits.push(matrice.modules[y][x]); }
+				}
+			}
+			if droite < 2 { break; }
+			droite -= 2;
+		}
+		let octets: Vec<u8> = bits.chunks(8).take(16).map(|c| c.iter().fold(0u8, |a, &b| a << 1 | u8::from(b))).collect();
+		// Mode octet (0100), longueur sur 8 bits, puis le texte décalé de 4 bits
+		assert_eq!(octets[0], 0x40 | (texte.len() >> 4) as u8);
+		let relu: Vec<u8> = (0..texte.len()).map(|i| octets[i + 1] << 4 | octets[i + 2] >> 4).collect();
+		assert_eq!(relu, texte);
+	}
+}
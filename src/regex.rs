@@ -0,0 +1,420 @@
+// regex.rs
+
+// Petit moteur d'expressions régulières sans dépendance. Le motif est compilé en automate
+// (Thompson) puis simulé sur tous les états à la fois (Pike): temps linéaire en la longueur
+// du texte, sans retour arrière ni récursion à l'exécution.
+// Syntaxe reconnue:
+//     littéraux, .  ^  $  \b  |  ( )  (?: )
+//     *  +  ?  {m}  {m,}  {m,n}  (suivis de '?' pour la version paresseuse, sans effet:
+//     seule compte la présence d'une correspondance)
+//     [abc]  [^a-z]  \d \w \s \D \W \S  et l'échappement des métacaractères.
+// Le texte et les littéraux passent par la même fonction de repli ('fold'):
+// la recherche reste insensible à la casse et, au besoin, aux accents.
+
+// --- 1. Structures de Données ---
+
+/// Erreur de compilation.
+#[derive(Debug)]
+pub enum RegexError {
+	Syntax(usize),	// Position (en caractères) où le motif devient invalide
+	TooComplex,		// L'automate dépasserait MAX_PROGRAM instructions
+}
+
+// Taille maximale de l'automate: borne les répétitions comme 'a{1000}{1000}'.
+const MAX_PROGRAM: usize = 20_000;
+
+#[derive(Debug, Clone)]
+enum ClassItem {
+	Char(char),
+	Range(char, char),
+	Digit(bool),	// bool: négation (\D)
+	Word(bool),
+	Space(bool),
+}
+
+#[derive(Debug)]
+enum Node {
+	Char(char),
+	Any,
+	Class(Vec<ClassItem>, bool),	// bool: classe niée [^...]
+	Start,
+	End,
+	WordBoundary,
+	Concat(Vec<Node>),
+	Alt(Vec<Node>),
+	Repeat { node: Box<Node>, min: usize, max: Option<usize> },
+}
+
+// Instructions de l'automate
+#[derive(Debug)]
+enum Inst {
+	Char(char),
+	Any,
+	Class(Vec<ClassItem>, bool),
+	Start,
+	End,
+	WordBoundary,
+	Split(usize, usize),	// Deux suites possibles
+	Jmp(usize),
+	Match,
+}
+
+#[derive(Debug)]
+pub struct Regex {
+	program: Vec<Inst>,
+	fold: fn(&str) -> String,
+}
+
+// --- 2. Compilation ---
+
+struct Parser<'a> {
+	chars: Vec<char>,
+	pos: usize,
+	fold: &'a dyn Fn(char) -> char,
+}
+
+impl Parser<'_> {
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.pos).copied()
+	}
+
+	fn eat(&mut self, c: char) -> bool {
+		if self.peek() == Some(c) { self.pos += 1; true } else { false }
+	}
+
+	fn error<T>(&self) -> Result<T, RegexError> {
+		Err(RegexError::Syntax(self.pos))
+	}
+
+	fn alternation(&mut self) -> Result<Node, RegexError> {
+		let mut branches = vec![self.concat()?];
+		while self.eat('|') {
+			branches.push(self.concat()?);
+		}
+		if branches.len() == 1 { Ok(branches.remove(0)) } else { Ok(Node::Alt(branches)) }
+	}
+
+	fn concat(&mut self) -> Result<Node, RegexError> {
+		let mut nodes = Vec::new();
+		while let Some(c) = self.peek() {
+			if c == '|' || c == ')' { break; }
+			let atom = self.atom()?;
+			nodes.push(self.quantifier(atom)?);
+		}
+		Ok(Node::Concat(nodes))
+	}
+
+	fn atom(&mut self) -> Result<Node, RegexError> {
+		let Some(c) = self.peek() else { return self.error() };
+		self.pos += 1;
+		match c {
+			'.' => Ok(Node::Any),
+			'^' => Ok(Node::Start),
+			'$' => Ok(Node::End),
+			'(' => {
+				// Groupe non capturant (?: ...) ou simple groupe: même traitement
+				if self.eat('?') && !self.eat(':') { return self.error(); }
+				let node = self.alternation()?;
+				if !self.eat(')') { return self.error(); }
+				Ok(node)
+			}
+			'[' => self.class(),
+			'\\' => {
+				let Some(e) = self.peek() else { return self.error() };
+				self.pos += 1;
+				match e {
+					'b' => Ok(Node::WordBoundary),
+					_ => match escape_class(e) {
+						Some(item) => Ok(Node::Class(vec![item], false)),
+						None => Ok(Node::Char((self.fold)(escape_char(e)))),
+					},
+				}
+			}
+			'*' | '+' | '?' | '{' | ')' => { self.pos -= 1; self.error() }
+			_ => Ok(Node::Char((self.fold)(c))),
+		}
+	}
+
+	fn class(&mut self) -> Result<Node, RegexError> {
+		let negated = self.eat('^');
+		let mut items = Vec::new();
+		let mut premier = true;
+		loop {
+			let Some(c) = self.peek() else { return self.error() };
+			self.pos += 1;
+			// ']' en première position est un littéral
+			if c == ']' && !premier { break; }
+			premier = false;
+
+			let debut = if c == '\\' {
+				let Some(e) = self.peek() else { return self.error() };
+				self.pos += 1;
+				if let Some(item) = escape_class(e) { items.push(item); continue; }
+				escape_char(e)
+			}
+			else { c };
+
+			// Intervalle a-z (un '-' final est un littéral)
+			if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|&n| n != ']') {
+				self.pos += 1;
+				let mut fin = self.chars[self.pos];
+				self.pos += 1;
+				if fin == '\\' {
+					let Some(e) = self.peek() else { return self.error() };
+					self.pos += 1;
+					fin = escape_char(e);
+				}
+				if fin < debut { return self.error(); }
+				items.push(ClassItem::Range((self.fold)(debut), (self.fold)(fin)));
+			}
+			else { items.push(ClassItem::Char((self.fold)(debut))); }
+		}
+		Ok(Node::Class(items, negated))
+	}
+
+	fn number(&mut self) -> Option<usize> {
+		let debut = self.pos;
+		while self.peek().is_some_and(|c| c.is_ascii_digit()) { self.pos += 1; }
+		self.chars[debut..self.pos].iter().collect::<String>().parse().ok()
+	}
+
+	fn quantifier(&mut self, atom: Node) -> Result<Node, RegexError> {
+		let (min, max) = match self.peek() {
+			Some('*') => { self.pos += 1; (0, None) }
+			Some('+') => { self.pos += 1; (1, None) }
+			Some('?') => { self.pos += 1; (0, Some(1)) }
+			Some('{') => {
+				self.pos += 1;
+				let Some(min) = self.number() else { return self.error() };
+				let max = if self.eat(',') {
+					if self.peek() == Some('}') { None } else { Some(self.number().ok_or(RegexError::Syntax(self.pos))?) }
+				}
+				else { Some(min) };
+				if !self.eat('}') || max.is_some_and(|m| m < min) { return self.error(); }
+				(min, max)
+			}
+			_ => return Ok(atom),
+		};
+		if matches!(atom, Node::Start | Node::End | Node::WordBoundary) { return self.error(); }
+		self.eat('?');		// Paresseux: même résultat pour une simple recherche
+		let node = Node::Repeat { node: Box::new(atom), min, max };
+		// Un quantificateur ne peut en suivre un autre directement
+		if matches!(self.peek(), Some('*' | '+' | '?' | '{')) { return self.error(); }
+		Ok(node)
+	}
+}
+
+fn escape_class(e: char) -> Option<ClassItem> {
+	match e {
+		'd' => Some(ClassItem::Digit(false)),
+		'D' => Some(ClassItem::Digit(true)),
+		'w' => Some(ClassItem::Word(false)),
+		'W' => Some(ClassItem::Word(true)),
+		's' => Some(ClassItem::Space(false)),
+		'S' => Some(ClassItem::Space(true)),
+		_ => None,
+	}
+}
+
+fn escape_char(e: char) -> char {
+	match e {
+		'n' => '\n',
+		't' => '\t',
+		_ => e,
+	}
+}
+
+impl Regex {
+	/// Compile le motif. 'fold' est appliquée aux littéraux et au texte recherché.
+	pub fn new(motif: &str, fold: fn(&str) -> String) -> Result<Self, RegexError> {
+		let fold_char = |c: char| fold(&c.to_string()).chars().next().unwrap_or(c);
+		let mut parser = Parser { chars: motif.chars().collect(), pos: 0, fold: &fold_char };
+		let root = parser.alternation()?;
+		if parser.pos < parser.chars.len() { return parser.error(); }
+
+		let mut program = Vec::new();
+		compile(&root, &mut program)?;
+		program.push(Inst::Match);
+		Ok(Self { program, fold })
+	}
+
+	/// Vrai si le motif apparaît n'importe où dans le texte.
+	pub fn is_match(&self, texte: &str) -> bool {
+		let texte: Vec<char> = (self.fold)(texte).chars().collect();
+		let mut vu = vec![usize::MAX; self.program.len()];
+		let mut courants = Vec::new();
+		for pos in 0..=texte.len() {
+			// Une nouvelle tentative commence à chaque position
+			if self.ajoute(0, pos, &texte, &mut courants, &mut vu) { return true; }
+			let Some(&c) = texte.get(pos) else { break };
+
+			let mut suivants = Vec::new();
+			for &pc in &courants {
+				let avance = match &self.program[pc] {
+					Inst::Char(x) => c == *x,
+					Inst::Any => c != '\n',
+					Inst::Class(items, negated) => items.iter().any(|i| class_item(i, c)) != *negated,
+					_ => false,
+				};
+				if avance && self.ajoute(pc + 1, pos + 1, &texte, &mut suivants, &mut vu) { return true; }
+			}
+			courants = suivants;
+		}
+		false
+	}
+
+	// Ajoute l'état 'pc' et ceux qu'il atteint sans consommer de caractère aux états en cours.
+	// 'vu' retient la position où chaque état a été ajouté: aucun n'est visité deux fois.
+	// Retourne vrai si l'état final est atteint.
+	fn ajoute(&self, pc: usize, pos: usize, texte: &[char], liste: &mut Vec<usize>, vu: &mut [usize]) -> bool {
+		let mut pile = vec![pc];
+		while let Some(pc) = pile.pop() {
+			if vu[pc] == pos { continue; }
+			vu[pc] = pos;
+			match self.program[pc] {
+				Inst::Match => return true,
+				Inst::Jmp(cible) => pile.push(cible),
+				Inst::Split(a, b) => { pile.push(b); pile.push(a); }
+				Inst::Start => if pos == 0 { pile.push(pc + 1); },
+				Inst::End => if pos == texte.len() { pile.push(pc + 1); },
+				Inst::WordBoundary => {
+					let avant = pos > 0 && est_mot(texte[pos - 1]);
+					let apres = texte.get(pos).is_some_and(|&c| est_mot(c));
+					if avant != apres { pile.push(pc + 1); }
+				}
+				_ => liste.push(pc),
+			}
+		}
+		false
+	}
+}
+
+// --- 3. Automate ---
+
+fn emit(program: &mut Vec<Inst>, inst: Inst) -> Result<usize, RegexError> {
+	if program.len() >= MAX_PROGRAM { return Err(RegexError::TooComplex); }
+	program.push(inst);
+	Ok(program.len() - 1)
+}
+
+fn compile(node: &Node, program: &mut Vec<Inst>) -> Result<(), RegexError> {
+	match node {
+		Node::Char(c) => { emit(program, Inst::Char(*c))?; }
+		Node::Any => { emit(program, Inst::Any)?; }
+		Node::Class(items, negated) => { emit(program, Inst::Class(items.clone(), *negated))?; }
+		Node::Start => { emit(program, Inst::Start)?; }
+		Node::End => { emit(program, Inst::End)?; }
+		Node::WordBoundary => { emit(program, Inst::WordBoundary)?; }
+		Node::Concat(nodes) => {
+			for n in nodes { compile(n, program)?; }
+		}
+		Node::Alt(branches) => {
+			// split L1, L2; L1: branche; jmp fin; L2: branche suivante...
+			let mut sauts = Vec::new();
+			for (i, b) in branches.iter().enumerate() {
+				if i + 1 < branches.len() {
+					let split = emit(program, Inst::Split(0, 0))?;
+					compile(b, program)?;
+					sauts.push(emit(program, Inst::Jmp(0))?);
+					program[split] = Inst::Split(split + 1, program.len());
+				}
+				else { compile(b, program)?; }
+			}
+			let fin = program.len();
+			for s in sauts { program[s] = Inst::Jmp(fin); }
+		}
+		Node::Repeat { node, min, max } => {
+			// Un corps vide (ex. '()') se répète sans rien produire: inutile d'insister
+			let debut = program.len();
+			compile(node, program)?;
+			let vide = program.len() == debut;
+			if *min == 0 { program.truncate(debut); }
+			for _ in 1..*min {
+				if vide { break; }
+				compile(node, program)?;
+			}
+			if vide { return Ok(()); }
+			match max {
+				// L: split corps, fin; corps; jmp L
+				None => {
+					let split = emit(program, Inst::Split(0, 0))?;
+					compile(node, program)?;
+					emit(program, Inst::Jmp(split))?;
+					program[split] = Inst::Split(split + 1, program.len());
+				}
+				// Chaque répétition facultative peut mener directement à la fin
+				Some(max) => {
+					let mut splits = Vec::new();
+					for _ in *min..*max {
+						splits.push(emit(program, Inst::Split(0, 0))?);
+						compile(node, program)?;
+					}
+					let fin = program.len();
+					for s in splits { program[s] = Inst::Split(s + 1, fin); }
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+fn est_mot(c: char) -> bool {
+	c.is_alphanumeric() || c == '_'
+}
+
+fn class_item(item: &ClassItem, c: char) -> bool {
+	match *item {
+		ClassItem::Char(x) => c == x,
+		ClassItem::Range(a, b) => a <= c && c <= b,
+		ClassItem::Digit(neg) => c.is_ascii_digit() != neg,
+		ClassItem::Word(neg) => est_mot(c) != neg,
+		ClassItem::Space(neg) => c.is_whitespace() != neg,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn regex(motif: &str) -> Regex {
+		Regex::new(motif, |t| t.to_lowercase()).expect("motif valide")
+	}
+
+	#[test]
+	fn correspondances() {
+		assert!(regex("^VPN-").is_match("vpn-Paris"));
+		assert!(!regex("^VPN-").is_match("mon vpn-paris"));
+		assert!(regex(r"\.gouv\.qc\.ca$").is_match("https://www.revenu.gouv.qc.ca"));
+		assert!(regex("chat|chien").is_match("Le CHIEN"));
+		assert!(regex("^a(?:bc)+d$").is_match("abcbcd"));
+		assert!(regex(r"^\d{3}-\d{4}$").is_match("555-1234"));
+		assert!(!regex(r"^\d{3}-\d{4}$").is_match("555-123"));
+		assert!(regex("^x{2,3}$").is_match("xxx") && !regex("^x{2,3}$").is_match("xxxx"));
+		assert!(regex("[^a-z]").is_match("abc1") && !regex("[^a-z]").is_match("abc"));
+		assert!(regex("[]-]").is_match("a-b"));
+		assert!(regex(r"\bmot\b").is_match("un mot ici") && !regex(r"\bmot\b").is_match("motif"));
+		assert!(regex("^$").is_match(""));
+		assert!(!regex("a.b").is_match("a\nb"));
+	}
+
+	#[test]
+	fn motifs_invalides() {
+		for motif in ["(", "a)", "[a", "*a", "a**", "a{2,1}", "a{", "(?x)", "\\", "^*", "[z-a]"] {
+			assert!(matches!(Regex::new(motif, |t| t.to_string()), Err(RegexError::Syntax(_))), "{motif}");
+		}
+		assert!(matches!(Regex::new("ab(c", |t| t.to_string()), Err(RegexError::Syntax(4))));
+		assert!(matches!(Regex::new("(a{1000}){1000}", |t| t.to_string()), Err(RegexError::TooComplex)));
+	}
+
+	#[test]
+	fn sans_retour_arriere_exponentiel() {
+		// Avec retour arrière, '(a*)*b' essaie 2^n découpages avant d'échouer
+		let texte = "a".repeat(100_000);
+		assert!(!regex("(a*)*b").is_match(&texte));
+		assert!(!regex("(a|aa)+$x").is_match(&texte));
+		assert!(regex("(a*)*$").is_match(&texte));
+		// Corps de répétition vide: ni boucle infinie ni débordement de pile
+		assert!(regex("(|a)*b").is_match("aab"));
+		assert!(regex("(()*)*x").is_match("x"));
+	}
+}
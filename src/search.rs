@@ -0,0 +1,359 @@
+// search.rs
+
+use crate::date;
+use crate::entry::{self, Entry};
+use crate::regex::{Regex, RegexError};
+use crate::template;
+
+// --- 1. Structures de Données ---
+
+// Champ visé par un terme de recherche. 'Any' couvre tous les champs descriptifs,
+// jamais le mot de passe ni les champs secrets d'un type ou personnalisés.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+	Any,
+	Title,
+	User,
+	Url,
+	Tag,
+	Notes,
+	Type,
+	Folder,
+}
+
+/// Options de recherche venant de la ligne de commande.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options {
+	pub exact_accents: bool,
+	pub regex: bool,
+	pub stable: bool,		// Sans bonus d'usage récent: avec --index, un même N désigne toujours la même entrée
+}
+
+/// Filtres exacts de --tag et --folder: une entrée qui n'y répond pas est écartée
+/// avant le pointage.
+#[derive(Debug, Clone, Default)]
+pub struct Filters {
+	pub tags: Vec<String>,
+	pub folder: Option<String>,
+}
+
+#[derive(Debug)]
+struct Term {
+	field: Field,
+	text: String,
+}
+
+/// Motif de recherche découpé en termes. Tous les termes doivent correspondre,
+/// de façon approximative (sous-séquence ou faute de frappe).
+/// En mode '--regex', le motif entier est une expression régulière.
+#[derive(Debug)]
+pub struct Query {
+	source: String,
+	terms: Vec<Term>,
+	regex: Option<Regex>,
+	options: Options,
+	filters: Filters,
+}
+
+// --- 2. Analyse du motif ---
+
+fn selector(nom: &str) -> Option<Field> {
+	match nom {
+		"title" => Some(Field::Title),
+		"user" => Some(Field::User),
+		"url" => Some(Field::Url),
+		"tag" => Some(Field::Tag),
+		"notes" => Some(Field::Notes),
+		"type" => Some(Field::Type),
+		"folder" => Some(Field::Folder),
+		_ => None,
+	}
+}
+
+impl Query {
+	/// 'gmail user:bob' => 'gmail' dans un champ descriptif ET 'bob' dans le nom d'usager.
+	pub fn parse(ptrn: &str, options: Options) -> Result<Self, RegexError> {
+		if options.regex {
+			let fold = if options.exact_accents { fold_case } else { fold_accents };
+			let regex = Regex::new(ptrn, fold)?;
+			return Ok(Self { source: ptrn.to_string(), terms: Vec::new(), regex: Some(regex), options, filters: Filters::default() });
+		}
+
+		let mut terms = Vec::new();
+		for mot in ptrn.split_whitespace() {
+			let term = match mot.split_once(':') {
+				Some((nom, texte)) if selector(nom).is_some() => Term {
+					field: selector(nom).unwrap_or(Field::Any),
+					text: normalize(texte, options.exact_accents),
+				},
+				_ => Term { field: Field::Any, text: normalize(mot, options.exact_accents) },
+			};
+			terms.push(term);
+		}
+		Ok(Self { source: ptrn.to_string(), terms, regex: None, options, filters: Filters::default() })
+	}
+
+	pub fn with_filters(mut self, filters: Filters) -> Self {
+		self.filters = filters;
+		self
+	}
+
+	/// Motif tel que saisi par l'usager.
+	pub fn source(&self) -> &str {
+		&self.source
+	}
+
+	/// Pertinence de l'entrée pour ce motif, ou None si un terme ne correspond pas.
+	/// Un motif vide correspond à tout avec un score nul.
+	pub fn score(&self, entry: &Entry) -> Option<i64> {
+		if !self.filters.accept(entry) { return None; }
+		if let Some(regex) = &self.regex {
+			let trouve = field_values(entry, Field::Any).iter().any(|v| regex.is_match(v));
+			return trouve.then(|| self.recency_bonus(entry));
+		}
+
+		let mut total = 0;
+		for term in &self.terms {
+			let motif: Vec<char> = term.text.chars().collect();
+			let mut meilleur: Option<i64> = None;
+			for (n, valeur) in field_values(entry, term.field).into_iter().enumerate() {
+				let texte: Vec<char> = normalize(valeur, self.options.exact_accents).chars().collect();
+				if let Some(mut s) = score_term(&motif, &texte) {
+					// Une correspondance dans le titre (toujours en tête) compte davantage
+					if n == 0 && matches!(term.field, Field::Any | Field::Title) { s += BONUS_TITRE; }
+					meilleur = Some(meilleur.map_or(s, |m| m.max(s)));
+				}
+			}
+			total += meilleur?;
+		}
+		Some(total + self.recency_bonus(entry))
+	}
+
+	fn recency_bonus(&self, entry: &Entry) -> i64 {
+		if self.options.stable { 0 } else { recency_bonus(entry) }
+	}
+}
+
+impl Filters {
+	/// Chaque étiquette demandée est portée par l'entrée (casse et accents ignorés),
+	/// et le dossier demandé est une suite de segments de son chemin:
+	/// 'banque' retient 'banque', 'banque/cartes' et 'perso/banque'.
+	fn accept(&self, entry: &Entry) -> bool {
+		let etiquettes: Vec<String> = entry.tags().iter().map(|t| normalize(t, false)).collect();
+		if !self.tags.iter().all(|t| etiquettes.contains(&normalize(t, false))) { return false; }
+		match &self.folder {
+			Some(dossier) => {
+				let chemin = format!("/{}/", normalize(entry.folder().unwrap_or_default(), false));
+				chemin.contains(&format!("/{}/", normalize(dossier, false)))
+			}
+			None => true,
+		}
+	}
+}
+
+/// Index des entrées qui correspondent au motif, triées par pertinence décroissante.
+/// À score égal, l'ordre du fichier est conservé.
+pub fn rank(query: &Query, list: &[Entry]) -> Vec<usize> {
+	let mut scores: Vec<(usize, i64)> = list.iter().enumerate()
+		.filter_map(|(i, entry)| query.score(entry).map(|s| (i, s)))
+		.collect();
+	scores.sort_by_key(|&(_, s)| std::cmp::Reverse(s));
+	scores.into_iter().map(|(i, _)| i).collect()
+}
+
+// --- 3. Normalisation Unicode ---
+
+// Lettres accentuées (déjà en minuscules) et leur lettre de base: Latin-1, Latin étendu A et B,
+// Latin étendu additionnel (vietnamien, translittérations), grec monotonique et cyrillique.
+// Les autres écritures (grec polytonique, arabe, hébreu, indiennes...) restent telles quelles.
+const SANS_ACCENT: &[(&str, char)] = &[
+	("àáâãäåāăąǎǟǡǻȁȃȧḁạảấầẩẫậắằẳẵặ", 'a'), ("ḃḅḇ", 'b'), ("çćĉċčḉ", 'c'),
+	("ďđḋḍḏḑḓ", 'd'), ("èéêëēĕėęěȅȇȩḕḗḙḛḝẹẻẽếềểễệ", 'e'), ("ḟ", 'f'),
+	("ĝğġģǧǵḡ", 'g'), ("ĥħȟḣḥḧḩḫẖ", 'h'), ("ìíîïĩīĭįıǐȉȋḭḯỉị", 'i'), ("ĵǰ", 'j'),
+	("ķǩḱḳḵ", 'k'), ("ĺļľŀłḷḹḻḽ", 'l'), ("ḿṁṃ", 'm'), ("ñńņňŉǹṅṇṉṋ", 'n'),
+	("òóôõöøōŏőơǒǫǭȍȏȫȭȯȱṍṏṑṓọỏốồổỗộớờởỡợ", 'o'), ("ṕṗ", 'p'), ("ŕŗřȑȓṙṛṝṟ", 'r'),
+	("śŝşšșṡṣṥṧṩ", 's'), ("ţťŧțṫṭṯṱẗ", 't'), ("ùúûüũūŭůűųưǔǖǘǚǜȕȗṳṵṷṹṻụủứừửữự", 'u'),
+	("ṽṿ", 'v'), ("ŵẁẃẅẇẉẘ", 'w'), ("ẋẍ", 'x'), ("ýÿŷȳẏẙỳỵỷỹ", 'y'), ("źżžẑẓẕ", 'z'),
+	("ά", 'α'), ("έ", 'ε'), ("ή", 'η'), ("ίϊΐ", 'ι'), ("ό", 'ο'), ("ύϋΰ", 'υ'), ("ώ", 'ω'), ("ς", 'σ'),
+	("ѐё", 'е'), ("ѓ", 'г'), ("ї", 'і'), ("ѝй", 'и'), ("ќ", 'к'), ("ў", 'у'), ("ӂ", 'ж'), ("ӧ", 'о'),
+];
+
+/// Forme de comparaison d'un texte: minuscules (repli de casse) et, sauf si
+/// 'exact_accents', lettres sans leurs diacritiques ('Société' => 'societe', 'Việt' => 'viet', 'Ελλάδα' => 'ελλαδα').
+/// La table SANS_ACCENT remplace la décomposition NFD pour le latin, le grec monotonique et le cyrillique
+/// seulement; les lettres des autres écritures sont comparées telles quelles.
+pub fn normalize(texte: &str, exact_accents: bool) -> String {
+	let mut sortie = String::with_capacity(texte.len());
+	for c in texte.chars().flat_map(char::to_lowercase) {
+		match c {
+			'ß' => sortie.push_str("ss"),
+			_ if exact_accents => sortie.push(c),
+			// Diacritiques combinants (texte déjà décomposé)
+			'\u{0300}'..='\u{036F}' => { }
+			'æ' => sortie.push_str("ae"),
+			'œ' => sortie.push_str("oe"),
+			_ => {
+				let base = SANS_ACCENT.iter()
+					.find(|(accents, _)| accents.contains(c))
+					.map_or(c, |&(_, base)| base);
+				sortie.push(base);
+			}
+		}
+	}
+	sortie
+}
+
+/// Pointage approximatif d'un texte libre (filtre du sélecteur interactif).
+pub fn fuzzy(motif: &str, texte: &str) -> Option<i64> {
+	let motif: Vec<char> = normalize(motif, false).chars().collect();
+	let texte: Vec<char> = normalize(texte, false).chars().collect();
+	score_term(&motif, &texte)
+}
+
+fn fold_accents(texte: &str) -> String {
+	normalize(texte, false)
+}
+
+fn fold_case(texte: &str) -> String {
+	normalize(texte, true)
+}
+
+// --- 4. Pointage approximatif ---
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_FRONTIERE: i64 = 8;
+const BONUS_CONSECUTIF: i64 = 4;
+const PENALITE_TROU: i64 = 3;
+const PENALITE_TROU_EXT: i64 = 1;
+const BONUS_TITRE: i64 = 10;
+const PENALITE_TYPO: i64 = 12;
+const BONUS_RECENT: i64 = 20;
+
+fn score_term(motif: &[char], texte: &[char]) -> Option<i64> {
+	if motif.is_empty() { return Some(0); }
+	if let Some(s) = fuzzy_score(motif, texte) { return Some(s); }
+
+	// Tolérance aux fautes de frappe: 1 erreur dès 4 caractères, 2 dès 8.
+	let tolerance = match motif.len() {
+		0..=3 => 0,
+		4..=7 => 1,
+		_ => 2,
+	};
+	let distance = typo_distance(motif, texte);
+	if distance <= tolerance {
+		Some(motif.len() as i64 * SCORE_MATCH / 2 - distance as i64 * PENALITE_TYPO)
+	}
+	else { None }
+}
+
+fn est_frontiere(texte: &[char], i: usize) -> bool {
+	i == 0 || !texte[i - 1].is_alphanumeric()
+}
+
+/// Correspondance en sous-séquence, façon fzf: on trouve la première occurrence
+/// complète, puis on remonte pour obtenir la fenêtre la plus courte avant de la noter.
+fn fuzzy_score(motif: &[char], texte: &[char]) -> Option<i64> {
+	// 1. Passe avant: fin de la première sous-séquence complète
+	let mut p = 0;
+	let mut fin = None;
+	for (i, &c) in texte.iter().enumerate() {
+		if c == motif[p] {
+			p += 1;
+			if p == motif.len() { fin = Some(i); break; }
+		}
+	}
+	let fin = fin?;
+
+	// 2. Passe arrière: début de la fenêtre la plus courte
+	let mut p = motif.len();
+	let mut debut = fin;
+	for i in (0..=fin).rev() {
+		if texte[i] == motif[p - 1] {
+			p -= 1;
+			if p == 0 { debut = i; break; }
+		}
+	}
+
+	// 3. Pointage de la fenêtre
+	let mut score = 0;
+	let mut p = 0;
+	let mut consecutif = false;
+	let mut dans_trou = false;
+	for i in debut..=fin {
+		if p < motif.len() && texte[i] == motif[p] {
+			score += SCORE_MATCH;
+			if est_frontiere(texte, i) { score += BONUS_FRONTIERE; }
+			if consecutif { score += BONUS_CONSECUTIF; }
+			consecutif = true;
+			dans_trou = false;
+			p += 1;
+		}
+		else {
+			score -= if dans_trou { PENALITE_TROU_EXT } else { PENALITE_TROU };
+			consecutif = false;
+			dans_trou = true;
+		}
+	}
+	Some(score)
+}
+
+/// Plus petite distance d'édition (avec transpositions) entre le motif
+/// et n'importe quelle sous-chaîne du texte.
+fn typo_distance(motif: &[char], texte: &[char]) -> usize {
+	let m = motif.len();
+	let n = texte.len();
+	// d[i][j]: distance entre motif[..i] et une sous-chaîne se terminant en texte[..j]
+	let mut d = vec![vec![0usize; n + 1]; m + 1];
+	for (i, ligne) in d.iter_mut().enumerate() { ligne[0] = i; }
+
+	for i in 1..=m {
+		for j in 1..=n {
+			let cout = if motif[i - 1] == texte[j - 1] { 0 } else { 1 };
+			let mut v = (d[i - 1][j] + 1)
+				.min(d[i][j - 1] + 1)
+				.min(d[i - 1][j - 1] + cout);
+			if i > 1 && j > 1 && motif[i - 1] == texte[j - 2] && motif[i - 2] == texte[j - 1] {
+				v = v.min(d[i - 2][j - 2] + 1);
+			}
+			d[i][j] = v;
+		}
+	}
+	d[m].iter().copied().min().unwrap_or(m)
+}
+
+/// Petit bonus décroissant pour les entrées utilisées récemment.
+fn recency_bonus(entry: &Entry) -> i64 {
+	match entry.get_time(entry::USED) {
+		Some(used) => {
+			let semaines = date::now().saturating_sub(used) / (7 * date::SECS_PER_DAY);
+			BONUS_RECENT / (1 + semaines as i64)
+		}
+		None => 0,
+	}
+}
+
+// --- 5. Extraction des champs ---
+
+/// Valeurs d'un champ. Pour 'Any', le titre vient toujours en premier.
+pub fn field_values(entry: &Entry, field: Field) -> Vec<&str> {
+	match field {
+		Field::Title => vec![entry.title.as_str()],
+		Field::User => entry.get(entry::USER).into_iter().collect(),
+		Field::Url => entry.get(entry::URL).into_iter().collect(),
+		Field::Tag => entry.tags(),
+		Field::Notes => entry.get(entry::NOTES).into_iter().collect(),
+		Field::Type => vec![template::of(entry).name],
+		Field::Folder => entry.folder().into_iter().collect(),
+		Field::Any => {
+			let mut valeurs = vec![entry.title.as_str()];
+			valeurs.extend(entry.get(entry::USER));
+			valeurs.extend(entry.get(entry::URL));
+			valeurs.extend(entry.tags());
+			valeurs.extend(entry.folder());
+			valeurs.extend(entry.get(entry::NOTES));
+			valeurs.extend(template::searchable_values(entry));
+			valeurs.extend(entry.custom_fields().into_iter().filter(|&(_, _, secret)| !secret).map(|(_, v, _)| v));
+			valeurs
+		}
+	}
+}
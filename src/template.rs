@@ -0,0 +1,106 @@
+// template.rs
+
+// Types d'entrées et leurs champs. Une entrée sans champ 'type' est un identifiant (login).
+// Le mot de passe de l'entrée reste son secret principal, nommé selon le type
+// (NIP d'une carte, clé d'un réseau Wi-Fi...). Un type sans secret principal le laisse vide
+// et garde son contenu dans un champ secret (note, codes de récupération).
+
+use crate::entry::{self, Entry};
+
+pub struct TemplateField {
+	pub key: &'static str,
+	pub secret: bool,		// Masqué à l'affichage, jamais cherché
+}
+
+pub struct Template {
+	pub name: &'static str,
+	pub pw_label: Option<&'static str>,	// Nom du mot de passe; None: pas de secret principal
+	pub fields: &'static [TemplateField],
+}
+
+const fn champ(key: &'static str, secret: bool) -> TemplateField {
+	TemplateField { key, secret }
+}
+
+pub const LOGIN: &str = "login";
+
+pub const TEMPLATES: [Template; 7] = [
+	Template { name: LOGIN, pw_label: Some("password"), fields: &[] },
+	Template { name: "note", pw_label: None, fields: &[champ("content", true)] },
+	Template { name: "card", pw_label: Some("pin"),
+			   fields: &[champ("holder", false), champ("number", true), champ("expiry", false), champ("cvv", true)] },
+	Template { name: "wifi", pw_label: Some("key"), fields: &[champ("ssid", false), champ("security", false)] },
+	Template { name: "licence", pw_label: Some("key"),
+			   fields: &[champ("product", false), champ("version", false), champ("licensee", false)] },
+	Template { name: "ssh", pw_label: Some("passphrase"), fields: &[champ("keyfile", false), champ("fingerprint", false)] },
+	Template { name: "recovery", pw_label: None, fields: &[champ("codes", true)] },
+];
+
+pub fn find(nom: &str) -> Option<&'static Template> {
+	TEMPLATES.iter().find(|t| t.name == nom)
+}
+
+/// Type de l'entrée; un type inconnu est traité comme un identifiant.
+pub fn of(entry: &Entry) -> &'static Template {
+	entry.get(entry::TYPE).and_then(find).unwrap_or(&TEMPLATES[0])
+}
+
+pub fn names() -> Vec<&'static str> {
+	TEMPLATES.iter().map(|t| t.name).collect()
+}
+
+/// Vrai si 'cle' est un champ d'un des types.
+pub fn is_field(cle: &str) -> bool {
+	TEMPLATES.iter().any(|t| t.fields.iter().any(|c| c.key == cle))
+}
+
+/// Secret principal de l'entrée (copié ou révélé par --find): le mot de passe,
+/// ou, pour un type sans mot de passe, son premier champ secret.
+pub fn main_secret(entry: &Entry) -> &str {
+	if !entry.pw.is_empty() { return &entry.pw; }
+	of(entry).fields.iter()
+		.filter(|c| c.secret)
+		.find_map(|c| entry.get(c.key))
+		.unwrap_or_default()
+}
+
+/// Champs à afficher, dans l'ordre du type: (nom, valeur, secret). Le mot de passe vient en premier,
+/// puis les champs du type et les champs personnalisés, le secret 2FA en dernier.
+pub fn display_fields(entry: &Entry) -> Vec<(&str, &str, bool)> {
+	let modele = of(entry);
+	let mut champs = Vec::new();
+	if let Some(label) = modele.pw_label.filter(|_| !entry.pw.is_empty()) {
+		champs.push((label, entry.pw.as_str(), true));
+	}
+	for c in modele.fields {
+		if let Some(valeur) = entry.get(c.key) {
+			champs.push((c.key, valeur, c.secret));
+		}
+	}
+	champs.extend(entry.custom_fields());
+	if let Some(valeur) = entry.get(entry::OTP) {
+		champs.push((entry::OTP, valeur, true));
+	}
+	champs
+}
+
+/// Valeurs des champs non secrets du type, pour la recherche.
+pub fn searchable_values(entry: &Entry) -> Vec<&str> {
+	of(entry).fields.iter()
+		.filter(|c| !c.secret)
+		.filter_map(|c| entry.get(c.key))
+		.collect()
+}
+
+/// Nouvelle entrée du type donné; 'secret' devient son secret principal.
+pub fn new_entry(titre: &str, secret: &str, modele: &Template) -> Entry {
+	let mut entry = Entry::new(titre, "");
+	if modele.name != LOGIN {
+		entry.set(entry::TYPE, modele.name);
+	}
+	match (modele.pw_label, modele.fields.iter().find(|c| c.secret)) {
+		(None, Some(c)) => entry.set(c.key, secret),
+		_ => entry.pw = secret.to_string(),
+	}
+	entry
+}